@@ -0,0 +1,64 @@
+//! AnyItem enum covering every kind of item.
+
+use crate::prelude::*;
+
+#[non_exhaustive]
+#[derive(Debug)]
+/// An item of any kind, for when the kind is not known up front.
+pub enum AnyItem {
+    /// A [Story] linking to a URL.
+    Story(Story),
+    /// A [Story] without a URL, such as an Ask HN.
+    Ask(Ask),
+    /// A [Job] posting.
+    Job(Job),
+    /// A [Poll].
+    Poll(Poll),
+    /// A [Poll Option](PollOption).
+    PollOption(PollOption),
+    /// A [Comment].
+    Comment(Comment),
+    /// An item that was deleted, only its id remains.
+    Deleted(u64),
+}
+
+impl AnyItem {
+    /// Returns the unique id of the item.
+    pub fn id(&self) -> u64 {
+        match self {
+            AnyItem::Story(story) => story.id,
+            AnyItem::Ask(ask) => ask.id,
+            AnyItem::Job(job) => job.id,
+            AnyItem::Poll(poll) => poll.id,
+            AnyItem::PollOption(option) => option.id,
+            AnyItem::Comment(comment) => comment.id,
+            AnyItem::Deleted(id) => *id,
+        }
+    }
+
+    /// Returns the username of the [User] that posted the item, unless it was deleted.
+    pub fn by(&self) -> Option<&str> {
+        match self {
+            AnyItem::Story(story) => Some(&story.by),
+            AnyItem::Ask(ask) => Some(&ask.by),
+            AnyItem::Job(job) => Some(&job.by),
+            AnyItem::Poll(poll) => Some(&poll.by),
+            AnyItem::PollOption(option) => Some(&option.by),
+            AnyItem::Comment(comment) => Some(&comment.by),
+            AnyItem::Deleted(_) => None,
+        }
+    }
+
+    /// Returns when the item was posted as a Unix timestamp, unless it was deleted.
+    pub fn time(&self) -> Option<u64> {
+        match self {
+            AnyItem::Story(story) => Some(story.time),
+            AnyItem::Ask(ask) => Some(ask.time),
+            AnyItem::Job(job) => Some(job.time),
+            AnyItem::Poll(poll) => Some(poll.time),
+            AnyItem::PollOption(option) => Some(option.time),
+            AnyItem::Comment(comment) => Some(comment.time),
+            AnyItem::Deleted(_) => None,
+        }
+    }
+}
@@ -36,4 +36,34 @@ impl Ask {
             .map(|kid| client.get_comment(*kid))
             .collect()
     }
+
+    /// Returns the text of this submission decoded to plain text.
+    pub fn text_plain(&self) -> String {
+        Document::parse(&self.text).to_plain()
+    }
+
+    /// Returns the text of this submission converted to Markdown.
+    pub fn text_markdown(&self) -> String {
+        Document::parse(&self.text).to_markdown()
+    }
+
+    /// Returns the text of this submission as sanitized HTML.
+    pub fn text_html(&self) -> String {
+        Document::parse(&self.text).to_html()
+    }
+
+    /// Returns the links referenced in the text of this submission.
+    pub fn links(&self) -> Vec<Link> {
+        Document::parse(&self.text).links()
+    }
+
+    /// Returns the page of this submission on the Hacker News website.
+    pub fn permalink(&self) -> String {
+        self.permalink_on(&WebHost::default())
+    }
+
+    /// Returns the page of this submission on a different web host.
+    pub fn permalink_on(&self, host: &WebHost) -> String {
+        host.item(self.id)
+    }
 }
@@ -0,0 +1,496 @@
+//! Interactive terminal reader for Hacker News.
+//!
+//! Run as `hnews-tui [base-url]`, the base URL defaulting to the official API.
+
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use hnews::cache::CacheConfig;
+use hnews::list::PAGE_SIZE;
+use hnews::prelude::*;
+use std::collections::HashSet;
+use std::io::{self, Stdout, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+
+const LISTS: [StoryList; 6] = [
+    StoryList::Top,
+    StoryList::New,
+    StoryList::Best,
+    StoryList::Ask,
+    StoryList::Show,
+    StoryList::Job,
+];
+
+const LIST_HELP: &str = "←/→ list  ↑/↓ move  enter comments  o open  n/p page  r refresh  q quit";
+const THREAD_HELP: &str =
+    "↑/↓ move  space collapse  o open comment  O open story  r refresh  esc back  q quit";
+
+/// Puts the terminal back into its normal state, even when panicking.
+struct Terminal {
+    out: Stdout,
+}
+
+impl Terminal {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let mut out = io::stdout();
+        execute!(out, EnterAlternateScreen, Hide)?;
+        Ok(Self { out })
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = execute!(self.out, Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// A comment of the open thread as laid out on screen.
+struct Row {
+    id: u64,
+    depth: usize,
+    header: String,
+    text: String,
+    hidden: usize,
+}
+
+struct ThreadView {
+    thread: Thread,
+    collapsed: HashSet<u64>,
+    cursor: usize,
+}
+
+impl ThreadView {
+    fn new(thread: Thread) -> Self {
+        Self {
+            thread,
+            collapsed: HashSet::new(),
+            cursor: 0,
+        }
+    }
+
+    /// Returns the visible comments, skipping the replies of collapsed ones.
+    fn rows(&self) -> Vec<Row> {
+        fn visit(nodes: &[CommentNode], depth: usize, view: &ThreadView, rows: &mut Vec<Row>) {
+            for node in nodes {
+                let collapsed = view.collapsed.contains(&node.id);
+                let (header, text) = match &node.comment {
+                    None => ("[deleted]".to_string(), String::new()),
+                    Some(comment) => {
+                        let dead = if comment.dead { " [dead]" } else { "" };
+                        (format!("{}{}", comment.by, dead), comment.text_plain())
+                    }
+                };
+                rows.push(Row {
+                    id: node.id,
+                    depth,
+                    header,
+                    text,
+                    hidden: if collapsed { node.descendants() } else { 0 },
+                });
+                if !collapsed {
+                    visit(&node.children, depth + 1, view, rows);
+                }
+            }
+        }
+        let mut rows = vec![];
+        visit(&self.thread.comments, 0, self, &mut rows);
+        rows
+    }
+}
+
+enum View {
+    List,
+    Thread(Box<ThreadView>),
+}
+
+struct App {
+    client: Client,
+    list: usize,
+    page: usize,
+    items: Vec<AnyItem>,
+    selected: usize,
+    view: View,
+    status: String,
+}
+
+impl App {
+    fn new(client: Client) -> Self {
+        Self {
+            client,
+            list: 0,
+            page: 1,
+            items: vec![],
+            selected: 0,
+            view: View::List,
+            status: String::new(),
+        }
+    }
+
+    fn load_list(&mut self, term: &mut Terminal, fresh: bool) {
+        self.loading(term);
+        let client = if fresh {
+            self.client.uncached()
+        } else {
+            self.client.clone()
+        };
+        match client.get_page(LISTS[self.list], self.page) {
+            Ok(page) => {
+                self.items = page.items;
+                self.selected = self.selected.min(self.items.len().saturating_sub(1));
+                self.status.clear();
+            }
+            Err(e) => self.status = format!("error: {}", e),
+        }
+    }
+
+    fn load_thread(&mut self, term: &mut Terminal, id: u64, fresh: bool) {
+        self.loading(term);
+        let client = if fresh {
+            self.client.uncached()
+        } else {
+            self.client.clone()
+        };
+        match client.get_thread(id) {
+            Ok(thread) => {
+                let mut view = ThreadView::new(thread);
+                if let View::Thread(previous) = &self.view {
+                    if previous.thread.root.id() == id {
+                        view.collapsed = previous.collapsed.clone();
+                        view.cursor = previous.cursor;
+                    }
+                }
+                self.view = View::Thread(Box::new(view));
+                self.status.clear();
+            }
+            Err(e) => self.status = format!("error: {}", e),
+        }
+    }
+
+    fn loading(&mut self, term: &mut Terminal) {
+        self.status = "loading...".to_string();
+        let _ = self.draw(term);
+    }
+
+    /// Handles a key press, returning false once the reader should quit.
+    fn key(&mut self, term: &mut Terminal, key: KeyEvent) -> bool {
+        let ctrl_c =
+            key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+        if key.code == KeyCode::Char('q') || ctrl_c {
+            return false;
+        }
+        match &mut self.view {
+            View::List => match key.code {
+                KeyCode::Left | KeyCode::Char('h') => {
+                    self.list = (self.list + LISTS.len() - 1) % LISTS.len();
+                    self.page = 1;
+                    self.selected = 0;
+                    self.load_list(term, false);
+                }
+                KeyCode::Right | KeyCode::Char('l') | KeyCode::Tab => {
+                    self.list = (self.list + 1) % LISTS.len();
+                    self.page = 1;
+                    self.selected = 0;
+                    self.load_list(term, false);
+                }
+                KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.selected = (self.selected + 1).min(self.items.len().saturating_sub(1))
+                }
+                KeyCode::Char('n') => {
+                    self.page += 1;
+                    self.selected = 0;
+                    self.load_list(term, false);
+                }
+                KeyCode::Char('p') if self.page > 1 => {
+                    self.page -= 1;
+                    self.selected = 0;
+                    self.load_list(term, false);
+                }
+                KeyCode::Enter | KeyCode::Char('c') => {
+                    if let Some(item) = self.items.get(self.selected) {
+                        let id = item.id();
+                        self.load_thread(term, id, false);
+                    }
+                }
+                KeyCode::Char('o') => {
+                    if let Some(item) = self.items.get(self.selected) {
+                        self.status = open(&link(item));
+                    }
+                }
+                KeyCode::Char('r') => self.load_list(term, true),
+                _ => {}
+            },
+            View::Thread(view) => {
+                let rows = view.rows();
+                match key.code {
+                    KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('h') | KeyCode::Left => {
+                        self.view = View::List;
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => view.cursor = view.cursor.saturating_sub(1),
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        view.cursor = (view.cursor + 1).min(rows.len().saturating_sub(1))
+                    }
+                    KeyCode::Char(' ') | KeyCode::Enter => {
+                        if let Some(row) = rows.get(view.cursor) {
+                            if !view.collapsed.remove(&row.id) {
+                                view.collapsed.insert(row.id);
+                            }
+                        }
+                    }
+                    KeyCode::Char('o') => {
+                        let url = match rows.get(view.cursor) {
+                            Some(row) => WebHost::default().item(row.id),
+                            None => link(&view.thread.root),
+                        };
+                        self.status = open(&url);
+                    }
+                    KeyCode::Char('O') => self.status = open(&link(&view.thread.root)),
+                    KeyCode::Char('r') => {
+                        let id = view.thread.root.id();
+                        self.load_thread(term, id, true);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        true
+    }
+
+    fn draw(&self, term: &mut Terminal) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let (width, height) = (width as usize, height as usize);
+        let mut lines: Vec<(String, bool)> = vec![];
+        let body = height.saturating_sub(3);
+
+        let tabs: Vec<String> = LISTS
+            .iter()
+            .enumerate()
+            .map(|(index, list)| {
+                if index == self.list {
+                    format!("[{}]", list.title())
+                } else {
+                    format!(" {} ", list.title())
+                }
+            })
+            .collect();
+        let title = format!("{}  page {}", tabs.join(""), self.page);
+
+        let help = match &self.view {
+            View::List => {
+                let offset = self.selected.saturating_sub(body.saturating_sub(1));
+                for (index, item) in self.items.iter().enumerate().skip(offset).take(body) {
+                    let rank = (self.page - 1) * PAGE_SIZE + index + 1;
+                    lines.push((
+                        format!("{:>3}. {}", rank, summary(item)),
+                        index == self.selected,
+                    ));
+                }
+                LIST_HELP
+            }
+            View::Thread(view) => {
+                let mut header: Vec<String> = vec![];
+                let root = &view.thread.root;
+                header.extend(wrap(&summary(root), width));
+                if let AnyItem::Story(story) = root {
+                    header.push(story.url.clone());
+                }
+                let text = match root {
+                    AnyItem::Ask(ask) => ask.text_plain(),
+                    AnyItem::Poll(poll) => poll.text_plain(),
+                    _ => String::new(),
+                };
+                for line in text.lines() {
+                    header.extend(wrap(line, width));
+                }
+                header.push(String::new());
+
+                let mut comments: Vec<(String, bool)> = vec![];
+                let mut cursor_line = 0;
+                for (index, row) in view.rows().iter().enumerate() {
+                    let indent = "  ".repeat(row.depth.min(width / 4));
+                    let selected = index == view.cursor;
+                    if selected {
+                        cursor_line = comments.len();
+                    }
+                    let folded = if row.hidden > 0 {
+                        format!(" [+{}]", row.hidden)
+                    } else if view.collapsed.contains(&row.id) {
+                        " [+]".to_string()
+                    } else {
+                        String::new()
+                    };
+                    comments.push((format!("{}{}{}", indent, row.header, folded), selected));
+                    if !view.collapsed.contains(&row.id) {
+                        let room = width.saturating_sub(indent.len()).max(20);
+                        for paragraph in row.text.lines() {
+                            for line in wrap(paragraph, room) {
+                                comments.push((format!("{}{}", indent, line), false));
+                            }
+                        }
+                    }
+                    comments.push((String::new(), false));
+                }
+
+                let room = body.saturating_sub(header.len());
+                let offset = cursor_line.saturating_sub(room / 3);
+                lines.extend(header.into_iter().map(|line| (line, false)));
+                lines.extend(comments.into_iter().skip(offset).take(room));
+                THREAD_HELP
+            }
+        };
+
+        let out = &mut term.out;
+        queue!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+        queue!(
+            out,
+            SetAttribute(Attribute::Bold),
+            Print(clip(&title, width)),
+            SetAttribute(Attribute::Reset)
+        )?;
+        for (row, (line, selected)) in lines.iter().enumerate().take(body) {
+            queue!(out, MoveTo(0, (row + 2) as u16))?;
+            if *selected {
+                queue!(out, SetAttribute(Attribute::Reverse))?;
+            }
+            queue!(
+                out,
+                Print(clip(line, width)),
+                SetAttribute(Attribute::Reset)
+            )?;
+        }
+        let footer = if self.status.is_empty() {
+            help.to_string()
+        } else {
+            self.status.clone()
+        };
+        queue!(
+            out,
+            MoveTo(0, height.saturating_sub(1) as u16),
+            SetAttribute(Attribute::Dim),
+            Print(clip(&footer, width)),
+            SetAttribute(Attribute::Reset)
+        )?;
+        out.flush()
+    }
+}
+
+/// Returns a one line description of a list entry.
+fn summary(item: &AnyItem) -> String {
+    match item {
+        AnyItem::Story(story) => format!(
+            "{} ({}) - {} points by {}, {} comments",
+            story.title,
+            story.domain().unwrap_or_default(),
+            story.score,
+            story.by,
+            story.comments
+        ),
+        AnyItem::Ask(ask) => format!(
+            "{} - {} points by {}, {} comments",
+            ask.title, ask.score, ask.by, ask.comments
+        ),
+        AnyItem::Poll(poll) => format!(
+            "{} - {} points by {}, {} comments",
+            poll.title, poll.score, poll.by, poll.comments
+        ),
+        AnyItem::Job(job) => job.title.clone(),
+        AnyItem::Comment(comment) => format!("Comment by {}", comment.by),
+        item => format!("[deleted] {}", item.id()),
+    }
+}
+
+/// Returns where a list entry leads, falling back to its discussion page.
+fn link(item: &AnyItem) -> String {
+    match item {
+        AnyItem::Story(story) => story.url.clone(),
+        AnyItem::Job(Job { url: Some(url), .. }) if !url.is_empty() => url.clone(),
+        item => WebHost::default().item(item.id()),
+    }
+}
+
+fn clip(line: &str, width: usize) -> String {
+    line.chars().take(width).collect()
+}
+
+/// Wraps a line at word boundaries so no line is wider than `width`.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let length = line.chars().count();
+        if length > 0 && length + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Opens a URL in the default browser, returning a status message.
+///
+/// Only http and https URLs are opened, as story URLs are chosen by whoever submitted them.
+fn open(url: &str) -> String {
+    let lower = url.to_ascii_lowercase();
+    if !lower.starts_with("http://") && !lower.starts_with("https://") {
+        return format!(
+            "not opening {}: only http and https links are supported",
+            url
+        );
+    }
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        // Unlike `cmd /C start`, this does not interpret `&` and other shell characters.
+        let mut command = Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    let spawned = command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    match spawned {
+        Ok(mut child) => {
+            // Reaps the browser launcher once it exits, so it does not linger as a zombie.
+            thread::spawn(move || child.wait());
+            format!("opened {}", url)
+        }
+        Err(e) => format!("could not open {}: {}", url, e),
+    }
+}
+
+fn main() -> io::Result<()> {
+    let mut client = Client::new().with_cache(CacheConfig::default());
+    if let Some(base_url) = std::env::args().nth(1) {
+        client = client.with_base_url(&base_url);
+    }
+
+    let mut term = Terminal::enter()?;
+    let mut app = App::new(client);
+    app.load_list(&mut term, false);
+    loop {
+        app.draw(&mut term)?;
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press && !app.key(&mut term, key) => {
+                break;
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,490 @@
+//! Command-line interface for browsing and exporting Hacker News data.
+
+use hnews::cache::CacheConfig;
+use hnews::csv::{CsvRecord, CsvWriter};
+use hnews::prelude::*;
+use hnews::text::escape_markdown;
+use hnews::watch::{Event, Watcher};
+use miniserde::json::to_string;
+use std::io::{self, BufWriter, Write};
+use std::ops::ControlFlow;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: hnews <command> [options]
+
+Commands:
+    top, new, best, ask, show, jobs   List stories
+    item <id>                         Show a single item
+    thread <id>                       Show an item with all of its comments
+    user <name>                       Show a user profile
+    updates                           Show recently changed items and profiles
+    watch                             Print changes as they happen
+
+Options:
+    --format <format>    table, json, jsonl, csv or markdown [default: table]
+    --limit <n>          Show at most this many entries [default: 30]
+    --base-url <url>     Use a different API, such as a mirror
+    -h, --help           Show this message
+
+Exit codes:
+    0  Success
+    1  Unexpected error
+    2  Invalid arguments
+    3  The item or user does not exist
+    4  Network failure";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Table,
+    Json,
+    JsonLines,
+    Csv,
+    Markdown,
+}
+
+#[derive(Debug)]
+enum Command {
+    List(StoryList),
+    Item(u64),
+    Thread(u64),
+    User(String),
+    Updates,
+    Watch,
+}
+
+#[derive(Debug)]
+struct Options {
+    command: Command,
+    format: Format,
+    limit: usize,
+    base_url: Option<String>,
+}
+
+#[derive(Debug)]
+enum Error {
+    Usage(String),
+    Api(HError),
+}
+
+impl From<HError> for Error {
+    fn from(err: HError) -> Self {
+        Error::Api(err)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        HError::from(err).into()
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let result = parse(&args).and_then(|options| run(&options));
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(Error::Usage(message)) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            ExitCode::from(2)
+        }
+        Err(Error::Api(HError::Io(e))) if e.kind() == io::ErrorKind::BrokenPipe => {
+            ExitCode::SUCCESS
+        }
+        Err(Error::Api(e)) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(match e {
+                HError::NotFound => 3,
+                HError::UReq(_) | HError::Io(_) => 4,
+                _ => 1,
+            })
+        }
+    }
+}
+
+fn parse(args: &[String]) -> Result<Options, Error> {
+    let mut positional = vec![];
+    let mut format = Format::Table;
+    let mut limit = 30;
+    let mut base_url = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .cloned()
+                .ok_or_else(|| Error::Usage(format!("{} needs a value", name)))
+        };
+        match arg.as_str() {
+            "--format" => {
+                format = match value(arg)?.as_str() {
+                    "table" => Format::Table,
+                    "json" => Format::Json,
+                    "jsonl" => Format::JsonLines,
+                    "csv" => Format::Csv,
+                    "markdown" => Format::Markdown,
+                    other => return Err(Error::Usage(format!("unknown format `{}`", other))),
+                }
+            }
+            "--limit" => {
+                limit = value(arg)?
+                    .parse()
+                    .map_err(|_| Error::Usage("--limit needs a number".to_string()))?
+            }
+            "--base-url" => base_url = Some(value(arg)?),
+            flag if flag.starts_with("--") => {
+                return Err(Error::Usage(format!("unknown option `{}`", flag)))
+            }
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    let id = |value: Option<&&str>| -> Result<u64, Error> {
+        value
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| Error::Usage("expected an item id".to_string()))
+    };
+    let command = match positional.first().copied() {
+        Some("top") => Command::List(StoryList::Top),
+        Some("new") => Command::List(StoryList::New),
+        Some("best") => Command::List(StoryList::Best),
+        Some("ask") => Command::List(StoryList::Ask),
+        Some("show") => Command::List(StoryList::Show),
+        Some("jobs") => Command::List(StoryList::Job),
+        Some("item") => Command::Item(id(positional.get(1))?),
+        Some("thread") => Command::Thread(id(positional.get(1))?),
+        Some("user") => match positional.get(1) {
+            Some(name) => Command::User(name.to_string()),
+            None => return Err(Error::Usage("expected a username".to_string())),
+        },
+        Some("updates") => Command::Updates,
+        Some("watch") => Command::Watch,
+        Some(other) => return Err(Error::Usage(format!("unknown command `{}`", other))),
+        None => return Err(Error::Usage("expected a command".to_string())),
+    };
+    let expected = match command {
+        Command::Item(_) | Command::Thread(_) | Command::User(_) => 2,
+        _ => 1,
+    };
+    if positional.len() > expected {
+        return Err(Error::Usage(format!(
+            "unexpected argument `{}`",
+            positional[expected]
+        )));
+    }
+
+    Ok(Options {
+        command,
+        format,
+        limit,
+        base_url,
+    })
+}
+
+fn run(options: &Options) -> Result<(), Error> {
+    let mut client = Client::new().with_cache(CacheConfig::default());
+    if let Some(base_url) = &options.base_url {
+        client = client.with_base_url(base_url);
+    }
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+
+    match &options.command {
+        Command::List(list) => {
+            let mut ids = client.get_list(*list)?;
+            ids.truncate(options.limit);
+            let items = client
+                .get_items(&ids)
+                .into_iter()
+                .collect::<Result<Vec<_>, _>>()?;
+            write_items(&mut out, options.format, &items)?;
+        }
+        Command::Item(id) => {
+            let item = client.get_item(*id)?;
+            if options.format == Format::Json {
+                writeln!(out, "{}", to_string(&item))?;
+            } else {
+                write_items(&mut out, options.format, &[item])?;
+            }
+        }
+        Command::Thread(id) => match options.format {
+            Format::Table => write!(out, "{}", client.get_thread(*id)?.to_plain())?,
+            Format::Markdown => write!(out, "{}", client.get_thread(*id)?.to_markdown())?,
+            format => write_items(&mut out, format, &client.get_thread_items(*id)?)?,
+        },
+        Command::User(name) => {
+            let user = client.get_user(name)?;
+            write_user(&mut out, options.format, &user)?;
+        }
+        Command::Updates => {
+            let items = client.get_updates()?;
+            let profiles = client.get_user_updates()?;
+            write_updates(&mut out, options.format, &items, &profiles)?;
+        }
+        Command::Watch => {
+            let mut remaining = options.limit;
+            let mut failure = None;
+            Watcher::new(&client).run(|event| {
+                let written = write_event(&mut out, options.format, &event)
+                    .and_then(|_| out.flush().map_err(Error::from));
+                if let Err(e) = written {
+                    failure = Some(e);
+                    return ControlFlow::Break(());
+                }
+                remaining = remaining.saturating_sub(1);
+                if remaining == 0 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            })?;
+            if let Some(e) = failure {
+                return Err(e);
+            }
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn write_items(out: &mut impl Write, format: Format, items: &[Item]) -> Result<(), Error> {
+    match format {
+        Format::Json => {
+            let items: Vec<String> = items.iter().map(to_string).collect();
+            writeln!(out, "[{}]", items.join(","))?;
+        }
+        Format::JsonLines => {
+            for item in items {
+                writeln!(out, "{}", to_string(item))?;
+            }
+        }
+        Format::Csv => {
+            let mut writer = CsvWriter::<_, AnyItem>::new(&mut *out).plain_text();
+            for item in items {
+                writer.write(&item.clone().try_into()?)?;
+            }
+            writer.flush()?;
+        }
+        Format::Table => {
+            let rows = items
+                .iter()
+                .map(|item| {
+                    let any: AnyItem = item.clone().try_into()?;
+                    let title = any
+                        .field("title", true)
+                        .filter(|title| !title.is_empty())
+                        .or_else(|| any.field("text", true))
+                        .unwrap_or_default();
+                    Ok(vec![
+                        any.id().to_string(),
+                        any.field("type", true).unwrap_or_default(),
+                        any.field("score", true).unwrap_or_default(),
+                        any.field("comments", true).unwrap_or_default(),
+                        any.by().unwrap_or_default().to_string(),
+                        truncate(&title, 80),
+                    ])
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+            write_table(
+                out,
+                &["ID", "TYPE", "SCORE", "COMMENTS", "BY", "TITLE"],
+                &rows,
+            )?;
+        }
+        Format::Markdown => {
+            for (index, item) in items.iter().enumerate() {
+                let any: AnyItem = item.clone().try_into()?;
+                let permalink = WebHost::default().item(any.id());
+                let title = any.field("title", true).unwrap_or_default();
+                let url = any
+                    .field("url", true)
+                    .filter(|url| !url.is_empty())
+                    .unwrap_or_else(|| permalink.clone());
+                let score = any.field("score", true).unwrap_or_default();
+                writeln!(
+                    out,
+                    "{}. [{}]({}) ({} points by {}, [discussion]({}))",
+                    index + 1,
+                    escape_markdown(&title),
+                    url,
+                    score,
+                    escape_markdown(any.by().unwrap_or("[deleted]")),
+                    permalink
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_user(out: &mut impl Write, format: Format, user: &User) -> Result<(), Error> {
+    match format {
+        Format::Json | Format::JsonLines => writeln!(out, "{}", to_string(user))?,
+        Format::Csv => {
+            let mut writer = CsvWriter::new(&mut *out).plain_text();
+            writer.write(user)?;
+            writer.flush()?;
+        }
+        Format::Table => {
+            let rows: Vec<Vec<String>> = ["id", "created", "karma", "submitted", "about"]
+                .iter()
+                .map(|column| {
+                    let value = user.field(column, true).unwrap_or_default();
+                    vec![column.to_string(), truncate(&value, 80)]
+                })
+                .collect();
+            write_table(out, &["FIELD", "VALUE"], &rows)?;
+        }
+        Format::Markdown => {
+            writeln!(
+                out,
+                "# [{}]({})\n",
+                escape_markdown(&user.id),
+                user.permalink()
+            )?;
+            writeln!(out, "- Karma: {}", user.karma)?;
+            writeln!(out, "- Created: {}\n", user.created)?;
+            if !user.about.is_empty() {
+                writeln!(out, "{}", user.about_markdown())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_updates(
+    out: &mut impl Write,
+    format: Format,
+    items: &[u64],
+    profiles: &[String],
+) -> Result<(), Error> {
+    match format {
+        Format::Json => {
+            let items: Vec<String> = items.iter().map(u64::to_string).collect();
+            let profiles: Vec<String> = profiles.iter().map(to_string).collect();
+            writeln!(
+                out,
+                "{{\"items\":[{}],\"profiles\":[{}]}}",
+                items.join(","),
+                profiles.join(",")
+            )?;
+        }
+        Format::JsonLines => {
+            for id in items {
+                writeln!(out, "{{\"item\":{}}}", id)?;
+            }
+            for name in profiles {
+                writeln!(out, "{{\"profile\":{}}}", to_string(name))?;
+            }
+        }
+        Format::Csv => {
+            writeln!(out, "kind,id\r")?;
+            for id in items {
+                writeln!(out, "item,{}\r", id)?;
+            }
+            for name in profiles {
+                writeln!(out, "profile,{}\r", name)?;
+            }
+        }
+        Format::Table | Format::Markdown => {
+            let rows: Vec<Vec<String>> = items
+                .iter()
+                .map(|id| vec!["item".to_string(), id.to_string()])
+                .chain(
+                    profiles
+                        .iter()
+                        .map(|name| vec!["profile".to_string(), name.clone()]),
+                )
+                .collect();
+            write_table(out, &["KIND", "ID"], &rows)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_event(out: &mut impl Write, format: Format, event: &Event) -> Result<(), Error> {
+    let json = matches!(format, Format::Json | Format::JsonLines);
+    match event {
+        Event::NewItem(id) if json => writeln!(out, "{{\"event\":\"new\",\"item\":{}}}", id)?,
+        Event::NewItem(id) => writeln!(out, "new item {}", id)?,
+        Event::ItemChanged(id) if json => {
+            writeln!(out, "{{\"event\":\"changed\",\"item\":{}}}", id)?
+        }
+        Event::ItemChanged(id) => writeln!(out, "changed item {}", id)?,
+        Event::ProfileChanged(name) if json => writeln!(
+            out,
+            "{{\"event\":\"profile\",\"user\":{}}}",
+            to_string(name)
+        )?,
+        Event::ProfileChanged(name) => writeln!(out, "changed profile {}", name)?,
+        Event::TopListChanged {
+            entered,
+            left,
+            moved,
+        } if json => {
+            let ids = |ids: &[u64]| ids.iter().map(u64::to_string).collect::<Vec<_>>().join(",");
+            let moved: Vec<String> = moved
+                .iter()
+                .map(|m| format!("{{\"id\":{},\"from\":{},\"to\":{}}}", m.id, m.from, m.to))
+                .collect();
+            writeln!(
+                out,
+                "{{\"event\":\"top\",\"entered\":[{}],\"left\":[{}],\"moved\":[{}]}}",
+                ids(entered),
+                ids(left),
+                moved.join(",")
+            )?
+        }
+        Event::TopListChanged {
+            entered,
+            left,
+            moved,
+        } => writeln!(
+            out,
+            "top stories changed: {} entered, {} left, {} moved",
+            entered.len(),
+            left.len(),
+            moved.len()
+        )?,
+        _ => {}
+    }
+    Ok(())
+}
+
+fn write_table(out: &mut impl Write, header: &[&str], rows: &[Vec<String>]) -> io::Result<()> {
+    let mut widths: Vec<usize> = header.iter().map(|title| title.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect();
+        padded.join("  ").trim_end().to_string()
+    };
+    writeln!(out, "{}", line(header.to_vec()))?;
+    for row in rows {
+        writeln!(out, "{}", line(row.iter().map(String::as_str).collect()))?;
+    }
+    Ok(())
+}
+
+/// Shortens text to a single line of at most `max` characters.
+fn truncate(text: &str, max: usize) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() <= max {
+        line
+    } else {
+        let mut short: String = line.chars().take(max.saturating_sub(3)).collect();
+        short.push_str("...");
+        short
+    }
+}
@@ -0,0 +1,328 @@
+//! Builders for fake items, users and threads, enabled with the `testing` feature.
+//!
+//! The typed structs cannot be constructed outside this crate, so tests build them
+//! here instead. Every builder starts from plausible defaults, and the raw [Items](Item)
+//! can seed a [Dataset](crate::testing::Dataset) served by a mock server.
+
+use crate::prelude::*;
+use crate::thread;
+use std::collections::HashMap;
+
+/// The timestamp fake items and users are created at unless set otherwise.
+const DEFAULT_TIME: u64 = 1_700_000_000;
+
+/// Builds a [Story], or the raw [Item] of one.
+#[derive(Debug, Clone)]
+pub struct StoryBuilder {
+    item: Item,
+}
+
+impl StoryBuilder {
+    /// Starts a story with the id, linking to `https://example.com/<id>`.
+    pub fn new(id: u64) -> Self {
+        Self {
+            item: Item {
+                id,
+                kind: Some("story".to_string()),
+                by: Some("author".to_string()),
+                time: Some(DEFAULT_TIME),
+                title: Some(format!("Story {}", id)),
+                url: Some(format!("https://example.com/{}", id)),
+                score: Some(1),
+                descendants: Some(0),
+                ..Item::default()
+            },
+        }
+    }
+
+    /// Sets the username of the author.
+    pub fn by(mut self, by: &str) -> Self {
+        self.item.by = Some(by.to_string());
+        self
+    }
+
+    /// Sets when the story was posted, as a Unix timestamp.
+    pub fn time(mut self, time: u64) -> Self {
+        self.item.time = Some(time);
+        self
+    }
+
+    /// Sets the title.
+    pub fn title(mut self, title: &str) -> Self {
+        self.item.title = Some(title.to_string());
+        self
+    }
+
+    /// Sets the URL the story links to.
+    pub fn url(mut self, url: &str) -> Self {
+        self.item.url = Some(url.to_string());
+        self
+    }
+
+    /// Sets the score.
+    pub fn score(mut self, score: i64) -> Self {
+        self.item.score = Some(score);
+        self
+    }
+
+    /// Sets the total amount of comments.
+    pub fn comments(mut self, comments: u64) -> Self {
+        self.item.descendants = Some(comments);
+        self
+    }
+
+    /// Sets the ids of the top-level comments.
+    pub fn kids(mut self, kids: &[u64]) -> Self {
+        self.item.kids = Some(kids.to_vec());
+        self
+    }
+
+    /// Returns the raw item.
+    pub fn item(&self) -> Item {
+        self.item.clone()
+    }
+
+    /// Returns the story.
+    pub fn build(&self) -> Story {
+        self.item()
+            .try_into()
+            .expect("a built story is always valid")
+    }
+}
+
+/// Builds a [Comment], or the raw [Item] of one.
+#[derive(Debug, Clone)]
+pub struct CommentBuilder {
+    item: Item,
+}
+
+impl CommentBuilder {
+    /// Starts a comment with the id, replying to the parent.
+    pub fn new(id: u64, parent: u64) -> Self {
+        Self {
+            item: Item {
+                id,
+                kind: Some("comment".to_string()),
+                by: Some("commenter".to_string()),
+                time: Some(DEFAULT_TIME),
+                text: Some(format!("Comment {}", id)),
+                parent: Some(parent),
+                ..Item::default()
+            },
+        }
+    }
+
+    /// Sets the username of the author.
+    pub fn by(mut self, by: &str) -> Self {
+        self.item.by = Some(by.to_string());
+        self
+    }
+
+    /// Sets when the comment was posted, as a Unix timestamp.
+    pub fn time(mut self, time: u64) -> Self {
+        self.item.time = Some(time);
+        self
+    }
+
+    /// Sets the text, in the HTML subset Hacker News uses.
+    pub fn text(mut self, text: &str) -> Self {
+        self.item.text = Some(text.to_string());
+        self
+    }
+
+    /// Marks the comment as dead.
+    pub fn dead(mut self) -> Self {
+        self.item.dead = Some(true);
+        self
+    }
+
+    /// Sets the ids of the replies.
+    pub fn kids(mut self, kids: &[u64]) -> Self {
+        self.item.kids = Some(kids.to_vec());
+        self
+    }
+
+    /// Returns the raw item.
+    pub fn item(&self) -> Item {
+        self.item.clone()
+    }
+
+    /// Returns the comment.
+    pub fn build(&self) -> Comment {
+        self.item()
+            .try_into()
+            .expect("a built comment is always valid")
+    }
+}
+
+/// Builds a [User].
+#[derive(Debug, Clone)]
+pub struct UserBuilder {
+    id: String,
+    created: u64,
+    karma: u64,
+    about: String,
+    submitted: Vec<u64>,
+}
+
+impl UserBuilder {
+    /// Starts a user with the username.
+    pub fn new(name: &str) -> Self {
+        Self {
+            id: name.to_string(),
+            created: DEFAULT_TIME,
+            karma: 1,
+            about: String::new(),
+            submitted: vec![],
+        }
+    }
+
+    /// Sets when the account was created, as a Unix timestamp.
+    pub fn created(mut self, created: u64) -> Self {
+        self.created = created;
+        self
+    }
+
+    /// Sets the karma.
+    pub fn karma(mut self, karma: u64) -> Self {
+        self.karma = karma;
+        self
+    }
+
+    /// Sets the profile description, in the HTML subset Hacker News uses.
+    pub fn about(mut self, about: &str) -> Self {
+        self.about = about.to_string();
+        self
+    }
+
+    /// Sets the ids of the submitted items, newest first.
+    pub fn submitted(mut self, submitted: &[u64]) -> Self {
+        self.submitted = submitted.to_vec();
+        self
+    }
+
+    /// Returns the user.
+    pub fn build(&self) -> User {
+        User {
+            id: self.id.clone(),
+            created: self.created,
+            karma: self.karma,
+            about: self.about.clone(),
+            submitted: self.submitted.clone(),
+        }
+    }
+}
+
+/// Builds a story with a tree of comments whose `kids`, `parent` and comment count agree.
+#[derive(Debug, Clone)]
+pub struct ThreadBuilder {
+    root: Item,
+    comments: Vec<Item>,
+    index: HashMap<u64, usize>,
+}
+
+impl ThreadBuilder {
+    /// Starts a thread below the story, dropping any kids it already had.
+    pub fn new(story: StoryBuilder) -> Self {
+        let mut root = story.item();
+        root.kids = None;
+        root.descendants = Some(0);
+        Self {
+            root,
+            comments: vec![],
+            index: HashMap::new(),
+        }
+    }
+
+    /// Returns the next free id after the story and all comments.
+    pub fn next_id(&self) -> u64 {
+        let max = self.comments.iter().map(|item| item.id).max();
+        max.unwrap_or(0).max(self.root.id) + 1
+    }
+
+    /// Adds a comment replying to the story or to an earlier comment, which must exist.
+    ///
+    /// The parent and kids of the comment are overwritten to keep the tree consistent.
+    pub fn reply(mut self, parent: u64, comment: CommentBuilder) -> Self {
+        let mut item = comment.item();
+        item.parent = Some(parent);
+        item.kids = None;
+        self.attach(parent, item);
+        self
+    }
+
+    /// Adds a deleted comment replying to the story or to an earlier comment.
+    pub fn deleted(mut self, parent: u64, id: u64) -> Self {
+        let item = Item {
+            id,
+            deleted: Some(true),
+            kind: Some("comment".to_string()),
+            time: Some(DEFAULT_TIME),
+            parent: Some(parent),
+            ..Item::default()
+        };
+        self.attach(parent, item);
+        self
+    }
+
+    /// Adds `breadth` replies to the story, each with `breadth` replies, `depth` levels deep.
+    ///
+    /// Ids are allocated after [next_id](ThreadBuilder::next_id) in breadth-first order.
+    pub fn generate(mut self, breadth: usize, depth: usize) -> Self {
+        let mut level = vec![self.root.id];
+        for _ in 0..depth {
+            let mut next = vec![];
+            for parent in level {
+                for _ in 0..breadth {
+                    let id = self.next_id();
+                    let by = format!("user{}", id % 10);
+                    self = self.reply(parent, CommentBuilder::new(id, parent).by(&by));
+                    next.push(id);
+                }
+            }
+            level = next;
+        }
+        self
+    }
+
+    fn attach(&mut self, parent: u64, item: Item) {
+        let id = item.id;
+        let parent = if parent == self.root.id {
+            &mut self.root
+        } else {
+            let index = *self
+                .index
+                .get(&parent)
+                .expect("the parent comment must be added first");
+            &mut self.comments[index]
+        };
+        parent.kids.get_or_insert_with(Vec::new).push(id);
+        self.index.insert(id, self.comments.len());
+        self.comments.push(item);
+        let descendants = self.root.descendants.unwrap_or(0);
+        self.root.descendants = Some(descendants + 1);
+    }
+
+    /// Returns the raw story followed by every comment, in the order they were added.
+    pub fn items(&self) -> Vec<Item> {
+        std::iter::once(self.root.clone())
+            .chain(self.comments.iter().cloned())
+            .collect()
+    }
+
+    /// Returns the thread, as [Client::get_thread] would.
+    pub fn build(&self) -> Thread {
+        let items: HashMap<u64, &Item> = self.comments.iter().map(|item| (item.id, item)).collect();
+        thread::build(self.root.clone(), |ids| {
+            ids.iter()
+                .map(|id| {
+                    items
+                        .get(id)
+                        .map(|item| (*item).clone())
+                        .ok_or(HError::NotFound)
+                })
+                .collect()
+        })
+        .expect("a built thread is always valid")
+    }
+}
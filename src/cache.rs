@@ -0,0 +1,185 @@
+//! In-memory cache of API responses.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Configuration of a [Cache].
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    /// The maximum amount of responses kept, the least recently used are evicted first.
+    pub capacity: usize,
+    /// How long item responses stay fresh.
+    pub item_ttl: Duration,
+    /// How long user responses stay fresh.
+    pub user_ttl: Duration,
+    /// How long list responses such as the top stories stay fresh.
+    pub list_ttl: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            item_ttl: Duration::from_secs(5 * 60),
+            user_ttl: Duration::from_secs(10 * 60),
+            list_ttl: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Hit and miss counters of a [Cache].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups answered from the cache.
+    pub hits: u64,
+    /// Lookups that had to go to the network, including expired entries.
+    pub misses: u64,
+    /// Entries dropped to stay within capacity.
+    pub evictions: u64,
+    /// Entries currently stored.
+    pub entries: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Key {
+    Item(u64),
+    User(String),
+    List(String),
+}
+
+#[derive(Debug)]
+struct Entry {
+    body: String,
+    expires: Instant,
+    tick: u64,
+}
+
+#[derive(Debug, Default)]
+struct Lru {
+    entries: HashMap<Key, Entry>,
+    order: BTreeMap<u64, Key>,
+    tick: u64,
+    stats: CacheStats,
+}
+
+impl Lru {
+    fn remove(&mut self, key: &Key) -> Option<Entry> {
+        let entry = self.entries.remove(key)?;
+        self.order.remove(&entry.tick);
+        Some(entry)
+    }
+
+    fn touch(&mut self, key: &Key) -> u64 {
+        self.tick += 1;
+        self.order.insert(self.tick, key.clone());
+        self.tick
+    }
+}
+
+/// A least-recently-used cache of raw API responses, shared by clones of a [Client](crate::Client).
+#[derive(Debug)]
+pub struct Cache {
+    config: CacheConfig,
+    inner: Mutex<Lru>,
+}
+
+impl Cache {
+    /// Creates an empty cache.
+    pub fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            inner: Mutex::new(Lru::default()),
+        }
+    }
+
+    /// Returns the configuration of this cache.
+    pub fn config(&self) -> &CacheConfig {
+        &self.config
+    }
+
+    pub(crate) fn get(&self, key: &Key) -> Option<String> {
+        let mut lru = self.inner.lock().unwrap();
+        let fresh = match lru.entries.get(key) {
+            Some(entry) => entry.expires > Instant::now(),
+            None => false,
+        };
+        if !fresh {
+            lru.remove(key);
+            lru.stats.misses += 1;
+            return None;
+        }
+
+        lru.stats.hits += 1;
+        let tick = lru.touch(key);
+        let entry = lru.entries.get_mut(key)?;
+        let old = std::mem::replace(&mut entry.tick, tick);
+        let body = entry.body.clone();
+        lru.order.remove(&old);
+        Some(body)
+    }
+
+    pub(crate) fn insert(&self, key: Key, body: String) {
+        if self.config.capacity == 0 {
+            return;
+        }
+        let ttl = match key {
+            Key::Item(_) => self.config.item_ttl,
+            Key::User(_) => self.config.user_ttl,
+            Key::List(_) => self.config.list_ttl,
+        };
+
+        let mut lru = self.inner.lock().unwrap();
+        lru.remove(&key);
+        while lru.entries.len() >= self.config.capacity {
+            let Some((_, oldest)) = lru.order.pop_first() else {
+                break;
+            };
+            lru.entries.remove(&oldest);
+            lru.stats.evictions += 1;
+        }
+        let tick = lru.touch(&key);
+        lru.entries.insert(
+            key,
+            Entry {
+                body,
+                expires: Instant::now() + ttl,
+                tick,
+            },
+        );
+    }
+
+    pub(crate) fn contains(&self, key: &Key) -> bool {
+        self.inner.lock().unwrap().entries.contains_key(key)
+    }
+
+    pub(crate) fn invalidate(&self, key: &Key) {
+        self.inner.lock().unwrap().remove(key);
+    }
+
+    /// Drops the cached item with the id, if any.
+    pub fn invalidate_item(&self, id: u64) {
+        self.invalidate(&Key::Item(id));
+    }
+
+    /// Drops the cached user with the username, if any.
+    pub fn invalidate_user(&self, name: &str) {
+        self.invalidate(&Key::User(name.to_string()));
+    }
+
+    /// Drops every cached response.
+    pub fn clear(&self) {
+        let mut lru = self.inner.lock().unwrap();
+        lru.entries.clear();
+        lru.order.clear();
+    }
+
+    /// Returns the hit and miss counters of this cache.
+    pub fn stats(&self) -> CacheStats {
+        let lru = self.inner.lock().unwrap();
+        CacheStats {
+            entries: lru.entries.len(),
+            ..lru.stats
+        }
+    }
+}
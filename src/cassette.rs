@@ -0,0 +1,150 @@
+//! Recording and replaying of API responses for deterministic tests.
+//!
+//! A [Client] with a recording [Cassette] saves the body of every response it fetches
+//! from the network. Replaying serves those bodies without a network, failing with
+//! [HError::Unrecorded] for anything the recording did not cover.
+//!
+//! ```no_run
+//! use hnews::cassette::Cassette;
+//! use hnews::prelude::*;
+//!
+//! // Once, against the real API.
+//! let client = Client::new().with_cassette(Cassette::record("tests/pg.jsonl")?);
+//! let user = client.get_user("pg")?;
+//! user.stories(&client)?;
+//! client.cassette().unwrap().save()?;
+//!
+//! // In the test suite, offline.
+//! let client = Client::new().with_cassette(Cassette::replay("tests/pg.jsonl")?);
+//! let user = client.get_user("pg")?;
+//! user.stories(&client)?;
+//! # Ok::<(), HError>(())
+//! ```
+
+use crate::prelude::*;
+use miniserde::json::{from_str, to_string};
+use miniserde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// One line of a cassette file.
+#[derive(Debug, Serialize, Deserialize)]
+struct Interaction {
+    path: String,
+    body: String,
+}
+
+/// Responses recorded to or replayed from a file, used through [Client::with_cassette].
+///
+/// The file holds one JSON object per line with the `path` requested, relative to the
+/// base URL, and the raw response `body`. Paths are sorted, so re-recording gives small
+/// diffs, and relative, so a cassette recorded against one server replays against any.
+/// When a path is fetched more than once while recording, the last response is kept.
+///
+/// Recording does not cover [subscriptions](Client::subscribe), which always fail while
+/// replaying.
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    recording: bool,
+    interactions: Mutex<BTreeMap<String, String>>,
+}
+
+impl Cassette {
+    /// Starts an empty recording, written to the file on [save](Cassette::save) or drop.
+    pub fn record(path: impl AsRef<Path>) -> Result<Self, HError> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        Ok(Self {
+            path,
+            recording: true,
+            interactions: Mutex::new(BTreeMap::new()),
+        })
+    }
+
+    /// Loads a recording from the file to replay it.
+    pub fn replay(path: impl AsRef<Path>) -> Result<Self, HError> {
+        let path = path.as_ref().to_path_buf();
+        let mut interactions = BTreeMap::new();
+        for line in fs::read_to_string(&path)?.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let interaction: Interaction = from_str(line)?;
+            interactions.insert(interaction.path, interaction.body);
+        }
+        Ok(Self {
+            path,
+            recording: false,
+            interactions: Mutex::new(interactions),
+        })
+    }
+
+    /// Returns the file of this cassette.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns whether responses are being recorded rather than replayed.
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Returns the amount of recorded responses.
+    pub fn len(&self) -> usize {
+        self.interactions.lock().unwrap().len()
+    }
+
+    /// Returns whether nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the recorded paths, sorted.
+    pub fn paths(&self) -> Vec<String> {
+        self.interactions.lock().unwrap().keys().cloned().collect()
+    }
+
+    /// Writes the recorded responses to the file, replacing its contents.
+    ///
+    /// Does nothing while replaying.
+    pub fn save(&self) -> Result<(), HError> {
+        if !self.recording {
+            return Ok(());
+        }
+        let mut out = String::new();
+        for (path, body) in self.interactions.lock().unwrap().iter() {
+            let interaction = Interaction {
+                path: path.clone(),
+                body: body.clone(),
+            };
+            out.push_str(&to_string(&interaction));
+            out.push('\n');
+        }
+        fs::write(&self.path, out)?;
+        Ok(())
+    }
+
+    pub(crate) fn insert(&self, path: &str, body: &str) {
+        let mut interactions = self.interactions.lock().unwrap();
+        interactions.insert(path.to_string(), body.to_string());
+    }
+
+    pub(crate) fn play(&self, path: &str) -> Result<String, HError> {
+        let interactions = self.interactions.lock().unwrap();
+        interactions
+            .get(path)
+            .cloned()
+            .ok_or_else(|| HError::Unrecorded(path.to_string()))
+    }
+}
+
+impl Drop for Cassette {
+    fn drop(&mut self) {
+        let _ = self.save();
+    }
+}
@@ -1,6 +1,16 @@
 //! Static http client for requests.
 
+use crate::cache::{Cache, CacheConfig, CacheStats, Key};
+use crate::cassette::Cassette;
+use crate::disk_cache::DiskCache;
+use crate::dump::Dump;
+use crate::invalidate::{Invalidated, Invalidation};
+use crate::list::PAGE_SIZE;
+use crate::parallel::{self, DEFAULT_THREADS};
 use crate::prelude::*;
+use crate::search::{self, RawNode, RawResults, Search, SearchResults, DEFAULT_SEARCH_URL};
+use crate::stream::{Path, Subscription};
+use crate::thread::{self, Thread};
 use arc_swap::ArcSwap;
 use miniserde::json::from_str;
 use once_cell::sync::Lazy;
@@ -10,9 +20,18 @@ use ureq::Agent;
 static STATIC_INSTANCE: Lazy<ArcSwap<Client>> = Lazy::new(|| ArcSwap::from_pointee(Client::new()));
 
 /// The client handling all requests.
-#[derive(Debug)]
+///
+/// Clones share the underlying connection pool and [Cache].
+#[derive(Debug, Clone)]
 pub struct Client {
     client: Agent,
+    endpoint: Endpoint,
+    search: Endpoint,
+    cache: Option<Arc<Cache>>,
+    disk: Option<Arc<DiskCache>>,
+    cassette: Option<Arc<Cassette>>,
+    dump: Option<Arc<Dump>>,
+    bypass: bool,
 }
 
 impl Default for Client {
@@ -26,21 +45,190 @@ impl Client {
     pub fn new() -> Self {
         Self {
             client: Agent::new(),
+            endpoint: Endpoint::default(),
+            search: Endpoint::new(DEFAULT_SEARCH_URL),
+            cache: None,
+            disk: None,
+            cassette: None,
+            dump: None,
+            bypass: false,
         }
     }
 
+    /// Sends requests to a different API, such as a local stand-in.
+    ///
+    /// The base URL includes the version, like `https://hacker-news.firebaseio.com/v0`.
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.endpoint = Endpoint::new(base_url);
+        self
+    }
+
+    /// Sends searches to a different Algolia API, such as a local stand-in.
+    ///
+    /// The base URL includes the version, like `https://hn.algolia.com/api/v1`.
+    pub fn with_search_url(mut self, base_url: &str) -> Self {
+        self.search = Endpoint::new(base_url);
+        self
+    }
+
+    /// Enables an in-memory [Cache] of responses with the configuration.
+    pub fn with_cache(mut self, config: CacheConfig) -> Self {
+        self.cache = Some(Arc::new(Cache::new(config)));
+        self
+    }
+
+    /// Returns the in-memory cache, if enabled.
+    pub fn cache(&self) -> Option<&Cache> {
+        self.cache.as_deref()
+    }
+
+    /// Enables a persistent [DiskCache], consulted after the in-memory cache.
+    pub fn with_disk_cache(mut self, disk: DiskCache) -> Self {
+        self.disk = Some(Arc::new(disk));
+        self
+    }
+
+    /// Returns the persistent cache, if enabled.
+    pub fn disk_cache(&self) -> Option<&DiskCache> {
+        self.disk.as_deref()
+    }
+
+    /// Records responses to, or replays them from, a [Cassette] instead of the network.
+    ///
+    /// Caches are still consulted first, so only actual network requests are recorded.
+    pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(Arc::new(cassette));
+        self
+    }
+
+    /// Returns the cassette, if enabled.
+    pub fn cassette(&self) -> Option<&Cassette> {
+        self.cassette.as_deref()
+    }
+
+    /// Answers every request from a [Dump], without any network access.
+    pub fn with_dump(mut self, dump: Dump) -> Self {
+        self.dump = Some(Arc::new(dump));
+        self
+    }
+
+    /// Returns the dump, if enabled.
+    pub fn dump(&self) -> Option<&Dump> {
+        self.dump.as_deref()
+    }
+
+    /// Returns a client sharing these caches that always goes to the network.
+    ///
+    /// Responses it fetches still refresh the caches.
+    pub fn uncached(&self) -> Self {
+        Self {
+            bypass: true,
+            ..self.clone()
+        }
+    }
+
+    /// Drops the cached item with the id from all enabled caches.
+    pub fn invalidate_item(&self, id: u64) {
+        self.invalidate(&Key::Item(id))
+    }
+
+    /// Drops the cached [User] with the username from all enabled caches.
+    pub fn invalidate_user(&self, name: &str) {
+        self.invalidate(&Key::User(name.to_string()))
+    }
+
+    /// Removing a file from the disk cache is best effort, like writing it.
+    fn invalidate(&self, key: &Key) {
+        if let Some(cache) = &self.cache {
+            cache.invalidate(key);
+        }
+        if let Some(disk) = &self.disk {
+            let _ = disk.invalidate(key);
+        }
+    }
+
+    /// Returns the hit and miss counters of the cache, if enabled.
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache.as_ref().map(|cache| cache.stats())
+    }
+
     /// Returns a static instance for ease of use.
     pub fn instance() -> Arc<Self> {
         STATIC_INSTANCE.load().clone()
     }
 
-    fn get_item(&self, id: u64) -> Result<Item, HError> {
-        let response = self.client.get(&Endpoint::item(id)).call()?.into_string()?;
+    fn fetch(&self, key: Key, url: &str) -> Result<String, HError> {
+        let cache = self.cache.as_ref();
+        let disk = self.disk.as_ref();
+        if !self.bypass {
+            if let Some(body) = cache.and_then(|cache| cache.get(&key)) {
+                return Ok(body);
+            }
+            if let Some(body) = disk.and_then(|disk| disk.get(&key)) {
+                if let Some(cache) = cache {
+                    cache.insert(key, body.clone());
+                }
+                return Ok(body);
+            }
+        }
+
+        let body = self.get(url)?;
+        if body.trim() != "null" {
+            if let Some(disk) = disk {
+                // A failed write only costs a later cache miss, the response is still good.
+                let _ = disk.insert(&key, &body);
+            }
+            if let Some(cache) = cache {
+                cache.insert(key, body.clone());
+            }
+        }
+        Ok(body)
+    }
+
+    fn get(&self, url: &str) -> Result<String, HError> {
+        if let Some(dump) = self.dump.as_deref() {
+            return Ok(dump.respond(self.endpoint.relative(url)));
+        }
+        self.call(&self.endpoint, url)
+    }
+
+    /// Requests the URL below the endpoint, through the [Cassette] if enabled.
+    fn call(&self, endpoint: &Endpoint, url: &str) -> Result<String, HError> {
+        let path = endpoint.relative(url);
+        match self.cassette.as_deref() {
+            Some(cassette) if !cassette.is_recording() => cassette.play(path),
+            cassette => {
+                let body = self.client.get(url).call()?.into_string()?;
+                if let Some(cassette) = cassette {
+                    cassette.insert(path, &body);
+                }
+                Ok(body)
+            }
+        }
+    }
+
+    fn fetch_list(&self, url: String) -> Result<String, HError> {
+        self.fetch(Key::List(url.clone()), &url)
+    }
+
+    /// Returns the raw [Item] with the id, whatever its kind.
+    pub fn get_item(&self, id: u64) -> Result<Item, HError> {
+        let response = self.fetch(Key::Item(id), &self.endpoint.item(id))?;
+        if response.trim() == "null" {
+            return Err(HError::NotFound);
+        }
 
         let item: Item = from_str(&response)?;
         Ok(item)
     }
 
+    /// Returns an item with the id, whatever its kind.
+    pub fn get_any(&self, id: u64) -> Result<AnyItem, HError> {
+        let item = self.get_item(id)?;
+        let any: AnyItem = item.try_into()?;
+        Ok(any)
+    }
+
     /// Returns an [Ask] with the id.
     pub fn get_ask(&self, id: u64) -> Result<Ask, HError> {
         let item = self.get_item(id)?;
@@ -83,13 +271,30 @@ impl Client {
         Ok(story)
     }
 
+    /// Returns the raw items with the ids, fetched concurrently and in the same order.
+    pub fn get_items(&self, ids: &[u64]) -> Vec<Result<Item, HError>> {
+        parallel::map(ids, DEFAULT_THREADS, |id| self.get_item(*id))
+    }
+
+    /// Returns an item with its whole tree of comments, fetching each level concurrently.
+    pub fn get_thread(&self, id: u64) -> Result<Thread, HError> {
+        let root = self.get_item(id)?;
+        thread::build(root, |ids| self.get_items(ids))
+    }
+
+    /// Returns the raw items of an item and its whole tree of comments, root first and the
+    /// comments depth-first, fetching each level concurrently.
+    pub fn get_thread_items(&self, id: u64) -> Result<Vec<Item>, HError> {
+        let root = self.get_item(id)?;
+        thread::items(root, |ids| self.get_items(ids))
+    }
+
     /// Returns a [User] with the specified username.
     pub fn get_user(&self, name: &str) -> Result<User, HError> {
-        let response = self
-            .client
-            .get(&Endpoint::user(name))
-            .call()?
-            .into_string()?;
+        let response = self.fetch(Key::User(name.to_string()), &self.endpoint.user(name))?;
+        if response.trim() == "null" {
+            return Err(HError::NotFound);
+        }
 
         let user: User = from_str(&response)?;
         Ok(user)
@@ -97,100 +302,180 @@ impl Client {
 
     /// Gets up to 200 of the latest [Asks](Ask).
     pub fn get_latest_asks(&self) -> Result<Vec<Ask>, HError> {
-        let response = self
-            .client
-            .get(&Endpoint::askstories())
-            .call()?
-            .into_string()?;
+        let response = self.fetch_list(self.endpoint.askstories())?;
         let ids: Vec<u64> = from_str(&response)?;
         ids.into_iter().map(|id| self.get_ask(id)).collect()
     }
 
     /// Gets up to 200 of the latest [Stories](Story).
     pub fn get_latest_stories(&self) -> Result<Vec<Story>, HError> {
-        let response = self
-            .client
-            .get(&Endpoint::showstories())
-            .call()?
-            .into_string()?;
+        let response = self.fetch_list(self.endpoint.showstories())?;
         let ids: Vec<u64> = from_str(&response)?;
         ids.into_iter().map(|id| self.get_story(id)).collect()
     }
 
     /// Gets up to 200 of the latest [Jobs](Job).
     pub fn get_latest_jobs(&self) -> Result<Vec<Job>, HError> {
-        let response = self
-            .client
-            .get(&Endpoint::askstories())
-            .call()?
-            .into_string()?;
+        let response = self.fetch_list(self.endpoint.jobstories())?;
         let ids: Vec<u64> = from_str(&response)?;
         ids.into_iter().map(|id| self.get_job(id)).collect()
     }
 
     /// Returns the latest item id, can be any of: [Story], [Ask], [Job].
     pub fn get_latest(&self) -> Result<u64, HError> {
-        let response = self
-            .client
-            .get(&Endpoint::maxitem())
-            .call()?
-            .into_string()?;
+        let response = self.fetch_list(self.endpoint.maxitem())?;
         let id: u64 = from_str(&response)?;
         Ok(id)
     }
 
     /// Returns up to 500 of the latest top item ids, can be any of: [Story], [Ask], [Job].
     pub fn get_top(&self) -> Result<Vec<u64>, HError> {
-        let response = self
-            .client
-            .get(&Endpoint::topstories())
-            .call()?
-            .into_string()?;
+        let response = self.fetch_list(self.endpoint.topstories())?;
         let ids: Vec<u64> = from_str(&response)?;
         Ok(ids)
     }
 
     /// Returns up to 500 of the latest item ids, can be any of: [Story], [Ask], [Job].
     pub fn get_new(&self) -> Result<Vec<u64>, HError> {
-        let response = self
-            .client
-            .get(&Endpoint::newstories())
-            .call()?
-            .into_string()?;
+        let response = self.fetch_list(self.endpoint.newstories())?;
         let ids: Vec<u64> = from_str(&response)?;
         Ok(ids)
     }
 
     /// Returns up to 500 of the latest best item ids, can be any of: [Story], [Ask], [Job].
     pub fn get_best(&self) -> Result<Vec<u64>, HError> {
-        let response = self
-            .client
-            .get(&Endpoint::beststories())
-            .call()?
-            .into_string()?;
+        let response = self.fetch_list(self.endpoint.beststories())?;
         let ids: Vec<u64> = from_str(&response)?;
         Ok(ids)
     }
 
+    /// Returns the ids of a [StoryList], in ranked order.
+    pub fn get_list(&self, list: StoryList) -> Result<Vec<u64>, HError> {
+        let response = self.fetch_list(list.url(&self.endpoint))?;
+        let ids: Vec<u64> = from_str(&response)?;
+        Ok(ids)
+    }
+
+    /// Returns a page of a [StoryList] with its items fetched concurrently.
+    ///
+    /// Pages are numbered from 1 and hold [PAGE_SIZE] items, like on the website.
+    pub fn get_page(&self, list: StoryList, number: usize) -> Result<Page, HError> {
+        let ids = self.get_list(list)?;
+        let start = number.saturating_sub(1).saturating_mul(PAGE_SIZE);
+        let ids = ids.get(start..).unwrap_or_default();
+        let ids = &ids[..ids.len().min(PAGE_SIZE)];
+        let items = parallel::map(ids, DEFAULT_THREADS, |id| self.get_any(*id))
+            .into_iter()
+            .collect::<Result<_, _>>()?;
+        Ok(Page {
+            list,
+            number: number.max(1),
+            items,
+        })
+    }
+
+    pub(crate) fn get_update(&self) -> Result<Update, HError> {
+        let response = self.fetch_list(self.endpoint.updates())?;
+        let update: Update = from_str(&response)?;
+        Ok(update)
+    }
+
     /// Returns [User] profiles that have changed.
     pub fn get_user_updates(&self) -> Result<Vec<String>, HError> {
-        let response = self
-            .client
-            .get(&Endpoint::updates())
-            .call()?
-            .into_string()?;
-        let update: Update = from_str(&response)?;
-        Ok(update.profiles)
+        Ok(self.get_update()?.profiles)
     }
 
     /// Returns item ids that have changed, can be any of: [Story], [Ask], [Job].
     pub fn get_updates(&self) -> Result<Vec<u64>, HError> {
-        let response = self
-            .client
-            .get(&Endpoint::updates())
-            .call()?
-            .into_string()?;
-        let update: Update = from_str(&response)?;
-        Ok(update.items)
+        Ok(self.get_update()?.items)
+    }
+
+    /// Reads the latest updates and invalidates exactly those items and profiles in the caches.
+    ///
+    /// With [Invalidation::Refresh] the entries that were cached are fetched again right away.
+    pub fn apply_updates(&self, mode: Invalidation) -> Result<Invalidated, HError> {
+        let fresh = self.uncached();
+        let update = fresh.get_update()?;
+
+        let items: Vec<Key> = update.items.iter().map(|id| Key::Item(*id)).collect();
+        let profiles: Vec<Key> = update
+            .profiles
+            .iter()
+            .map(|name| Key::User(name.clone()))
+            .collect();
+        let keys: Vec<Key> = items.into_iter().chain(profiles).collect();
+
+        let cached: Vec<&Key> = keys.iter().filter(|key| self.is_cached(key)).collect();
+        for key in &keys {
+            self.invalidate(key);
+        }
+
+        if mode == Invalidation::Refresh {
+            parallel::map(&cached, DEFAULT_THREADS, |key| match key {
+                Key::Item(id) => fresh.fetch(Key::Item(*id), &self.endpoint.item(*id)),
+                Key::User(name) => fresh.fetch(Key::User(name.clone()), &self.endpoint.user(name)),
+                Key::List(url) => fresh.fetch_list(url.clone()),
+            })
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+        }
+
+        Ok(Invalidated {
+            items: update.items,
+            profiles: update.profiles,
+        })
+    }
+
+    fn is_cached(&self, key: &Key) -> bool {
+        let in_memory = self.cache.as_ref().is_some_and(|cache| cache.contains(key));
+        let on_disk = self.disk.as_ref().is_some_and(|disk| disk.contains(key));
+        in_memory || on_disk
+    }
+
+    /// Returns an item with its whole tree of comments in a single request to the Algolia API.
+    ///
+    /// Algolia may lag behind and order replies differently than Hacker News does.
+    /// Fails with [HError::Unrecorded] while reading a [Dump].
+    pub fn get_algolia_thread(&self, id: u64) -> Result<Thread, HError> {
+        let response = self.get_search(&self.search.build(&format!("items/{}", id)))?;
+        let root: RawNode = from_str(&response)?;
+        search::thread(root)
+    }
+
+    /// Returns an item with its whole tree of comments, in a single request when possible.
+    ///
+    /// Tries [get_algolia_thread](Client::get_algolia_thread) first and falls back to
+    /// [get_thread](Client::get_thread) if Algolia fails for any reason.
+    pub fn get_thread_at_once(&self, id: u64) -> Result<Thread, HError> {
+        self.get_algolia_thread(id).or_else(|_| self.get_thread(id))
+    }
+
+    /// Requests a URL of the Algolia API, which a [Dump] cannot answer.
+    fn get_search(&self, url: &str) -> Result<String, HError> {
+        if self.dump.is_some() {
+            return Err(HError::Unrecorded(self.search.relative(url).to_string()));
+        }
+        self.call(&self.search, url)
+    }
+
+    /// Runs a [Search] against the Algolia API, which is never cached.
+    ///
+    /// Fails with [HError::Unrecorded] while reading a [Dump].
+    pub fn search(&self, search: &Search) -> Result<SearchResults, HError> {
+        let response = self.get_search(&search.url(&self.search))?;
+        let results: RawResults = from_str(&response)?;
+        results.try_into()
+    }
+
+    /// Subscribes to live changes of a [Path] through Server-Sent Events.
+    ///
+    /// Fails with [HError::Unrecorded] while replaying a [Cassette] or reading a [Dump].
+    pub fn subscribe(&self, path: Path) -> Result<Subscription, HError> {
+        let url = path.url(&self.endpoint);
+        let replaying = self.cassette.as_deref().is_some_and(|c| !c.is_recording());
+        if replaying || self.dump.is_some() {
+            return Err(HError::Unrecorded(self.endpoint.relative(&url).to_string()));
+        }
+        Subscription::connect(self.client.clone(), url, path)
     }
 }
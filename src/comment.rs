@@ -8,6 +8,8 @@ use crate::prelude::*;
 pub struct Comment {
     /// The username of the [User] that made this comment.
     pub by: String,
+    /// Whether this comment is dead, meaning flagged or killed by moderators.
+    pub dead: bool,
     /// The unique id of this comment.
     pub id: u64,
     pub(crate) kids: Vec<u64>,
@@ -37,4 +39,45 @@ impl Comment {
     pub fn children(&self) -> usize {
         self.kids.len()
     }
+
+    /// Returns the text of this comment decoded to plain text.
+    pub fn text_plain(&self) -> String {
+        Document::parse(&self.text).to_plain()
+    }
+
+    /// Returns the text of this comment converted to Markdown.
+    pub fn text_markdown(&self) -> String {
+        Document::parse(&self.text).to_markdown()
+    }
+
+    /// Returns the text of this comment as sanitized HTML.
+    pub fn text_html(&self) -> String {
+        Document::parse(&self.text).to_html()
+    }
+
+    /// Returns the links referenced in the text of this comment.
+    pub fn links(&self) -> Vec<Link> {
+        Document::parse(&self.text).links()
+    }
+
+    /// Returns the page of this comment on the Hacker News website.
+    pub fn permalink(&self) -> String {
+        self.permalink_on(&WebHost::default())
+    }
+
+    /// Returns the page of this comment on a different web host.
+    pub fn permalink_on(&self, host: &WebHost) -> String {
+        host.item(self.id)
+    }
+
+    /// Returns the page of the parent of this comment, scrolled to this comment.
+    pub fn context_permalink(&self) -> String {
+        self.context_permalink_on(&WebHost::default())
+    }
+
+    /// Returns the page of the parent of this comment on a different web host,
+    /// scrolled to this comment.
+    pub fn context_permalink_on(&self, host: &WebHost) -> String {
+        host.comment(self.parent, self.id)
+    }
 }
@@ -0,0 +1,383 @@
+//! Resumable crawler walking a range of item ids.
+
+use crate::prelude::*;
+use crate::sink::Sink;
+use std::collections::BTreeSet;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The progress of a running [Crawler].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    /// Items processed, including failed and missing ones.
+    pub done: u64,
+    /// Items that could not be fetched after all retries.
+    pub failed: u64,
+    /// Items that do not exist.
+    pub missing: u64,
+    /// Items that were already done when the crawl was resumed.
+    pub resumed: u64,
+    /// Items in the crawled range, including those done before resuming.
+    pub total: u64,
+    /// Time spent crawling since the crawl was started or resumed.
+    pub elapsed: Duration,
+}
+
+impl Progress {
+    /// Returns the amount of items processed per second since the crawl was started or resumed.
+    pub fn rate(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds == 0.0 {
+            0.0
+        } else {
+            (self.done - self.resumed) as f64 / seconds
+        }
+    }
+
+    /// Returns the estimated time until the crawl finishes, if anything was processed yet.
+    ///
+    /// Returns [None] when the estimate does not fit a [Duration].
+    pub fn eta(&self) -> Option<Duration> {
+        let rate = self.rate();
+        if rate == 0.0 {
+            return None;
+        }
+        let remaining = self.total.saturating_sub(self.done);
+        Duration::try_from_secs_f64(remaining as f64 / rate).ok()
+    }
+}
+
+/// The outcome of a finished crawl.
+#[derive(Debug, Clone)]
+pub struct Report {
+    /// The final progress.
+    pub progress: Progress,
+    /// Ids of the items that could not be fetched.
+    pub failed: Vec<u64>,
+}
+
+/// Walks a range of item ids concurrently, delivering every item to a [Sink].
+///
+/// With a checkpoint file the crawl can be interrupted and resumed later, skipping the
+/// items that were delivered and retrying the ones that failed. Items delivered after the
+/// last checkpoint, which is written about once per second, are delivered again.
+#[derive(Debug)]
+pub struct Crawler {
+    client: Client,
+    start: u64,
+    end: u64,
+    backwards: bool,
+    threads: usize,
+    retries: u32,
+    rate_limit: Option<f64>,
+    checkpoint: Option<PathBuf>,
+}
+
+impl Crawler {
+    /// Creates a crawler walking the range of ids forwards.
+    pub fn new(client: &Client, range: RangeInclusive<u64>) -> Self {
+        Self {
+            client: client.clone(),
+            start: *range.start(),
+            end: *range.end(),
+            backwards: false,
+            threads: 8,
+            retries: 3,
+            rate_limit: None,
+            checkpoint: None,
+        }
+    }
+
+    /// Walks the range from the highest id down instead.
+    pub fn backwards(mut self) -> Self {
+        self.backwards = true;
+        self
+    }
+
+    /// Sets how many items are fetched concurrently.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Sets how often a failed request is retried before the item is given up on.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Limits the amount of requests made per second across all threads.
+    pub fn rate_limit(mut self, per_second: f64) -> Self {
+        self.rate_limit = Some(per_second).filter(|rate| *rate > 0.0);
+        self
+    }
+
+    /// Persists progress to a file, resuming from it if it already exists.
+    pub fn checkpoint(mut self, path: impl AsRef<Path>) -> Self {
+        self.checkpoint = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    fn total(&self) -> u64 {
+        self.end
+            .checked_sub(self.start)
+            .map_or(0, |span| span.saturating_add(1))
+    }
+
+    fn id(&self, offset: u64) -> u64 {
+        if self.backwards {
+            self.end - offset
+        } else {
+            self.start + offset
+        }
+    }
+
+    fn offset(&self, id: u64) -> u64 {
+        if self.backwards {
+            self.end.saturating_sub(id)
+        } else {
+            id.saturating_sub(self.start)
+        }
+    }
+
+    fn load_checkpoint(&self) -> Result<Checkpoint, HError> {
+        let mut checkpoint = Checkpoint::default();
+        let Some(path) = &self.checkpoint else {
+            return Ok(checkpoint);
+        };
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(checkpoint),
+            Err(e) => return Err(e.into()),
+        };
+        let mut delivered = vec![];
+        for line in contents.lines() {
+            let (key, values) = line.split_once(' ').unwrap_or((line, ""));
+            let ids = values
+                .split_whitespace()
+                .map(|id| id.parse::<u64>().map_err(|_| HError::ConversionFailed))
+                .collect::<Result<Vec<u64>, _>>()?;
+            match (key, ids.as_slice()) {
+                ("done", []) => checkpoint.watermark = self.total(),
+                ("next", [id]) => checkpoint.watermark = self.offset(*id).min(self.total()),
+                ("delivered", _) => delivered = ids,
+                ("failed", _) => checkpoint.failed.extend(ids),
+                _ => return Err(HError::ConversionFailed),
+            }
+        }
+        checkpoint.delivered = delivered
+            .into_iter()
+            .filter(|id| (self.start..=self.end).contains(id))
+            .map(|id| self.offset(id))
+            .filter(|offset| *offset >= checkpoint.watermark)
+            .collect();
+        Ok(checkpoint)
+    }
+
+    fn save_checkpoint(
+        &self,
+        watermark: u64,
+        delivered: &BTreeSet<u64>,
+        failed: &BTreeSet<u64>,
+    ) -> Result<(), HError> {
+        let Some(path) = &self.checkpoint else {
+            return Ok(());
+        };
+        let mut contents = if watermark >= self.total() {
+            "done\n".to_string()
+        } else {
+            format!("next {}\n", self.id(watermark))
+        };
+        if !delivered.is_empty() {
+            let ids = delivered.iter().map(|offset| self.id(*offset));
+            contents.push_str(&format!("delivered {}\n", join(ids)));
+        }
+        if !failed.is_empty() {
+            contents.push_str(&format!("failed {}\n", join(failed.iter().copied())));
+        }
+        let temporary = path.with_extension("tmp");
+        fs::write(&temporary, contents)?;
+        fs::rename(&temporary, path)?;
+        Ok(())
+    }
+
+    fn fetch(&self, id: u64, limiter: &Limiter) -> Result<Item, HError> {
+        let mut attempt = 0;
+        loop {
+            limiter.wait();
+            match self.client.get_item(id) {
+                Err(HError::NotFound) => return Err(HError::NotFound),
+                Err(_) if attempt < self.retries => {
+                    thread::sleep(Duration::from_millis(250 << attempt.min(6)));
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Runs the crawl, calling `on_progress` about once per second.
+    ///
+    /// When resuming, the items that failed before are fetched again first.
+    /// Stops at the first error returned by the sink or while checkpointing.
+    pub fn run<S, F>(&self, mut sink: S, mut on_progress: F) -> Result<Report, HError>
+    where
+        S: Sink,
+        F: FnMut(&Progress),
+    {
+        let total = self.total();
+        let checkpoint = self.load_checkpoint()?;
+        let resumed = checkpoint.watermark + checkpoint.delivered.len() as u64;
+        let next = AtomicU64::new(checkpoint.watermark);
+        let retry = Mutex::new(checkpoint.failed.iter().copied().collect::<Vec<u64>>());
+        let stop = AtomicBool::new(false);
+        let limiter = Limiter::new(self.rate_limit);
+        let started = Instant::now();
+        // Retried ids are sent without an offset, they were passed by the watermark already.
+        let (sender, receiver) = mpsc::channel::<(Option<u64>, u64, Result<Item, HError>)>();
+
+        let mut progress = Progress {
+            done: resumed,
+            failed: 0,
+            missing: 0,
+            resumed,
+            total,
+            elapsed: Duration::ZERO,
+        };
+        let mut failed = BTreeSet::new();
+
+        let result = thread::scope(|scope| {
+            for _ in 0..self.threads {
+                let sender = sender.clone();
+                let (next, retry, stop, limiter) = (&next, &retry, &stop, &limiter);
+                let skip = &checkpoint.delivered;
+                scope.spawn(move || {
+                    while !stop.load(Ordering::Relaxed) {
+                        let retried = retry.lock().unwrap().pop();
+                        let (offset, id) = match retried {
+                            Some(id) => (None, id),
+                            None => {
+                                let offset = next.fetch_add(1, Ordering::Relaxed);
+                                if offset >= total {
+                                    break;
+                                }
+                                if skip.contains(&offset) {
+                                    continue;
+                                }
+                                (Some(offset), self.id(offset))
+                            }
+                        };
+                        let result = self.fetch(id, limiter);
+                        if sender.send((offset, id, result)).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            drop(sender);
+
+            let mut watermark = checkpoint.watermark;
+            let mut completed = checkpoint.delivered.clone();
+            let mut pending = checkpoint.failed.clone();
+            let mut reported = Instant::now();
+            let mut run = || -> Result<(), HError> {
+                for (offset, id, result) in receiver.iter() {
+                    match result {
+                        Ok(item) => sink.write_item(&item)?,
+                        Err(HError::NotFound) => progress.missing += 1,
+                        Err(_) => {
+                            progress.failed += 1;
+                            failed.insert(id);
+                        }
+                    }
+                    match offset {
+                        Some(offset) => {
+                            progress.done += 1;
+                            completed.insert(offset);
+                            while completed.remove(&watermark) {
+                                watermark += 1;
+                            }
+                        }
+                        None => {
+                            pending.remove(&id);
+                        }
+                    }
+
+                    if reported.elapsed() >= Duration::from_secs(1) {
+                        sink.flush()?;
+                        let unfinished = failed.union(&pending).copied().collect();
+                        self.save_checkpoint(watermark, &completed, &unfinished)?;
+                        progress.elapsed = started.elapsed();
+                        on_progress(&progress);
+                        reported = Instant::now();
+                    }
+                }
+                sink.flush()?;
+                let unfinished = failed.union(&pending).copied().collect();
+                self.save_checkpoint(watermark, &completed, &unfinished)?;
+                Ok(())
+            };
+            let result = run();
+            stop.store(true, Ordering::Relaxed);
+            result
+        });
+
+        progress.elapsed = started.elapsed();
+        result?;
+        on_progress(&progress);
+        Ok(Report {
+            progress,
+            failed: failed.into_iter().collect(),
+        })
+    }
+}
+
+/// The state recorded in a checkpoint file, with positions as offsets into the range.
+///
+/// The file has a line `next <id>` or `done`, followed by optional lines listing the ids
+/// `delivered` past the next one and the ids that `failed`.
+#[derive(Debug, Default)]
+struct Checkpoint {
+    watermark: u64,
+    delivered: BTreeSet<u64>,
+    failed: BTreeSet<u64>,
+}
+
+fn join(ids: impl Iterator<Item = u64>) -> String {
+    ids.map(|id| id.to_string()).collect::<Vec<_>>().join(" ")
+}
+
+/// Spaces out requests evenly to stay under a rate limit.
+struct Limiter {
+    interval: Option<Duration>,
+    next: Mutex<Instant>,
+}
+
+impl Limiter {
+    fn new(per_second: Option<f64>) -> Self {
+        Self {
+            // Rates too small for a Duration interval are treated as no limit.
+            interval: per_second.and_then(|rate| Duration::try_from_secs_f64(1.0 / rate).ok()),
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    fn wait(&self) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+        let slot = {
+            let mut next = self.next.lock().unwrap();
+            let slot = (*next).max(Instant::now());
+            *next = slot.checked_add(interval).unwrap_or(slot);
+            slot
+        };
+        thread::sleep(slot.saturating_duration_since(Instant::now()));
+    }
+}
@@ -0,0 +1,314 @@
+//! Streaming CSV export of the typed structs.
+
+use crate::prelude::*;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+
+/// A struct that can be written as a row of a CSV file.
+pub trait CsvRecord {
+    /// Every column this record has, in the default order.
+    fn columns() -> &'static [&'static str];
+
+    /// Returns the value of a column, or [None] if the record has no such column.
+    ///
+    /// HTML text, such as comments and profile descriptions, is decoded to plain
+    /// text if `plain` is set and kept as is otherwise.
+    fn field(&self, column: &str, plain: bool) -> Option<String>;
+}
+
+fn html(text: &str, plain: bool) -> String {
+    if plain {
+        Document::parse(text).to_plain()
+    } else {
+        text.to_string()
+    }
+}
+
+impl CsvRecord for Story {
+    fn columns() -> &'static [&'static str] {
+        &[
+            "id",
+            "by",
+            "time",
+            "title",
+            "url",
+            "domain",
+            "score",
+            "comments",
+            "permalink",
+        ]
+    }
+
+    fn field(&self, column: &str, _plain: bool) -> Option<String> {
+        Some(match column {
+            "id" => self.id.to_string(),
+            "by" => self.by.clone(),
+            "time" => self.time.to_string(),
+            "title" => self.title.clone(),
+            "url" => self.url.clone(),
+            "domain" => self.domain().unwrap_or_default(),
+            "score" => self.score.to_string(),
+            "comments" => self.comments.to_string(),
+            "permalink" => self.permalink(),
+            _ => return None,
+        })
+    }
+}
+
+impl CsvRecord for Comment {
+    fn columns() -> &'static [&'static str] {
+        &["id", "by", "time", "parent", "text", "replies", "permalink"]
+    }
+
+    fn field(&self, column: &str, plain: bool) -> Option<String> {
+        Some(match column {
+            "id" => self.id.to_string(),
+            "by" => self.by.clone(),
+            "time" => self.time.to_string(),
+            "parent" => self.parent.to_string(),
+            "text" => html(&self.text, plain),
+            "replies" => self.children().to_string(),
+            "permalink" => self.permalink(),
+            _ => return None,
+        })
+    }
+}
+
+impl CsvRecord for Job {
+    fn columns() -> &'static [&'static str] {
+        &[
+            "id",
+            "by",
+            "time",
+            "title",
+            "url",
+            "domain",
+            "text",
+            "score",
+            "permalink",
+        ]
+    }
+
+    fn field(&self, column: &str, plain: bool) -> Option<String> {
+        Some(match column {
+            "id" => self.id.to_string(),
+            "by" => self.by.clone(),
+            "time" => self.time.to_string(),
+            "title" => self.title.clone(),
+            "url" => self.url.clone().unwrap_or_default(),
+            "domain" => self.domain().unwrap_or_default(),
+            "text" => html(self.text.as_deref().unwrap_or_default(), plain),
+            "score" => self.score.to_string(),
+            "permalink" => self.permalink(),
+            _ => return None,
+        })
+    }
+}
+
+impl CsvRecord for Poll {
+    fn columns() -> &'static [&'static str] {
+        &[
+            "id",
+            "by",
+            "time",
+            "title",
+            "text",
+            "score",
+            "comments",
+            "options",
+            "permalink",
+        ]
+    }
+
+    fn field(&self, column: &str, plain: bool) -> Option<String> {
+        Some(match column {
+            "id" => self.id.to_string(),
+            "by" => self.by.clone(),
+            "time" => self.time.to_string(),
+            "title" => self.title.clone(),
+            "text" => html(&self.text, plain),
+            "score" => self.score.to_string(),
+            "comments" => self.comments.to_string(),
+            "options" => self.parts.len().to_string(),
+            "permalink" => self.permalink(),
+            _ => return None,
+        })
+    }
+}
+
+impl CsvRecord for User {
+    fn columns() -> &'static [&'static str] {
+        &["id", "created", "karma", "about", "submitted", "permalink"]
+    }
+
+    fn field(&self, column: &str, plain: bool) -> Option<String> {
+        Some(match column {
+            "id" => self.id.clone(),
+            "created" => self.created.to_string(),
+            "karma" => self.karma.to_string(),
+            "about" => html(&self.about, plain),
+            "submitted" => self.submitted.len().to_string(),
+            "permalink" => self.permalink(),
+            _ => return None,
+        })
+    }
+}
+
+impl CsvRecord for AnyItem {
+    fn columns() -> &'static [&'static str] {
+        &[
+            "id",
+            "type",
+            "by",
+            "time",
+            "title",
+            "url",
+            "text",
+            "score",
+            "comments",
+            "parent",
+            "permalink",
+        ]
+    }
+
+    fn field(&self, column: &str, plain: bool) -> Option<String> {
+        let kind = match self {
+            AnyItem::Story(_) => "story",
+            AnyItem::Ask(_) => "ask",
+            AnyItem::Job(_) => "job",
+            AnyItem::Poll(_) => "poll",
+            AnyItem::PollOption(_) => "pollopt",
+            AnyItem::Comment(_) => "comment",
+            AnyItem::Deleted(_) => "deleted",
+        };
+        let value = match (self, column) {
+            (_, "id") => Some(self.id().to_string()),
+            (_, "type") => Some(kind.to_string()),
+            (_, "by") => self.by().map(str::to_string),
+            (_, "time") => self.time().map(|time| time.to_string()),
+            (_, "permalink") => Some(WebHost::default().item(self.id())),
+            (AnyItem::Story(story), column) => story.field(column, plain),
+            (AnyItem::Ask(ask), "title") => Some(ask.title.clone()),
+            (AnyItem::Ask(ask), "text") => Some(html(&ask.text, plain)),
+            (AnyItem::Ask(ask), "score") => Some(ask.score.to_string()),
+            (AnyItem::Ask(ask), "comments") => Some(ask.comments.to_string()),
+            (AnyItem::Job(job), column) => job.field(column, plain),
+            (AnyItem::Poll(poll), column) => poll.field(column, plain),
+            (AnyItem::PollOption(option), "text") => Some(html(&option.text, plain)),
+            (AnyItem::PollOption(option), "score") => Some(option.score.to_string()),
+            (AnyItem::PollOption(option), "parent") => Some(option.poll.to_string()),
+            (AnyItem::Comment(comment), column) => comment.field(column, plain),
+            _ => None,
+        };
+        if Self::columns().contains(&column) {
+            Some(value.unwrap_or_default())
+        } else {
+            None
+        }
+    }
+}
+
+/// Writes records of one type as CSV following RFC 4180, one row at a time.
+///
+/// The header row is written before the first record, or on flush if there is none.
+#[derive(Debug)]
+pub struct CsvWriter<W: Write, T: CsvRecord> {
+    writer: BufWriter<W>,
+    columns: Vec<String>,
+    plain: bool,
+    header: bool,
+    record: PhantomData<fn(&T)>,
+}
+
+impl<T: CsvRecord> CsvWriter<File, T> {
+    /// Creates or truncates the file at the path and writes to it.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, HError> {
+        Ok(Self::new(File::create(path)?))
+    }
+}
+
+impl<W: Write, T: CsvRecord> CsvWriter<W, T> {
+    /// Writes to a writer with every column of the record, buffering the output.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: BufWriter::new(writer),
+            columns: T::columns().iter().map(|c| c.to_string()).collect(),
+            plain: false,
+            header: false,
+            record: PhantomData,
+        }
+    }
+
+    /// Selects which columns are written and in which order.
+    ///
+    /// Fails with [HError::UnknownColumn] if the record has no such column.
+    pub fn columns(mut self, columns: &[&str]) -> Result<Self, HError> {
+        if let Some(column) = columns.iter().find(|c| !T::columns().contains(c)) {
+            return Err(HError::UnknownColumn(column.to_string()));
+        }
+        self.columns = columns.iter().map(|c| c.to_string()).collect();
+        Ok(self)
+    }
+
+    /// Decodes HTML text to plain text instead of writing it as is.
+    pub fn plain_text(mut self) -> Self {
+        self.plain = true;
+        self
+    }
+
+    /// Writes a single record.
+    pub fn write(&mut self, record: &T) -> Result<(), HError> {
+        self.write_header()?;
+        let fields: Vec<String> = self
+            .columns
+            .iter()
+            .map(|column| record.field(column, self.plain).unwrap_or_default())
+            .collect();
+        write_row(&mut self.writer, &fields)
+    }
+
+    /// Flushes buffered rows to the underlying writer.
+    pub fn flush(&mut self) -> Result<(), HError> {
+        self.write_header()?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Flushes and returns the underlying writer.
+    pub fn into_inner(mut self) -> Result<W, HError> {
+        self.write_header()?;
+        self.writer
+            .into_inner()
+            .map_err(|e| HError::Io(e.into_error()))
+    }
+
+    fn write_header(&mut self) -> Result<(), HError> {
+        if !self.header {
+            write_row(&mut self.writer, &self.columns)?;
+            self.header = true;
+        }
+        Ok(())
+    }
+}
+
+fn write_row(writer: &mut impl Write, fields: &[String]) -> Result<(), HError> {
+    for (index, field) in fields.iter().enumerate() {
+        if index > 0 {
+            writer.write_all(b",")?;
+        }
+        writer.write_all(quote(field).as_bytes())?;
+    }
+    writer.write_all(b"\r\n")?;
+    Ok(())
+}
+
+/// Quotes a field if it contains a separator, quote or line break.
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,65 @@
+//! Formatting of Unix timestamps without pulling in a date library.
+
+/// Splits a Unix timestamp into year, month, day, hours, minutes, seconds and weekday.
+fn civil(time: u64) -> (u64, u64, u64, u64, u64, u64, usize) {
+    let days = time / 86_400;
+    let seconds = time % 86_400;
+    // Days to civil date, after Howard Hinnant's algorithm.
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    // The epoch was a Thursday.
+    let weekday = ((days + 4) % 7) as usize;
+    (
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        weekday,
+    )
+}
+
+/// Formats a Unix timestamp as an RFC 822 date, as used by RSS.
+pub(crate) fn rfc822(time: u64) -> String {
+    const DAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let (year, month, day, hours, minutes, seconds, weekday) = civil(time);
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} +0000",
+        DAYS[weekday],
+        day,
+        MONTHS[month as usize - 1],
+        year,
+        hours,
+        minutes,
+        seconds
+    )
+}
+
+/// Formats a Unix timestamp as an RFC 3339 date in UTC, as used by Atom.
+pub(crate) fn rfc3339(time: u64) -> String {
+    let (year, month, day, hours, minutes, seconds, _) = civil(time);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year, month, day, hours, minutes, seconds
+    )
+}
+
+/// Formats a Unix timestamp for people, such as `2007-04-04 19:16 UTC`.
+pub(crate) fn display(time: u64) -> String {
+    let (year, month, day, hours, minutes, _, _) = civil(time);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+        year, month, day, hours, minutes
+    )
+}
@@ -0,0 +1,179 @@
+//! Persistent cache of items and users in a directory of JSON files.
+
+use crate::cache::Key;
+use crate::prelude::*;
+use miniserde::json::from_str;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Items are stored in subdirectories of this many ids, to keep directories small.
+const BUCKET_SIZE: u64 = 10_000;
+
+/// Counts temporary files written by this process, to give each a unique name.
+static TEMPORARY_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// Configuration of a [DiskCache].
+#[derive(Debug, Clone)]
+pub struct DiskCacheConfig {
+    /// Items older than this are considered immutable and never fetched again.
+    ///
+    /// Younger items are always revalidated against the network.
+    pub immutable_after: Duration,
+    /// How long stored user profiles are served before being fetched again.
+    pub user_max_age: Duration,
+}
+
+impl Default for DiskCacheConfig {
+    fn default() -> Self {
+        Self {
+            immutable_after: Duration::from_secs(14 * 24 * 60 * 60),
+            user_max_age: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// A directory of raw item and user JSON, consulted by a [Client] before the network.
+///
+/// Items are stored as `items/<bucket>/<id>.json` and users as `users/<name>.json`.
+/// Usernames are case-sensitive, so in file names `_` is written as `__` and upper-case
+/// letters as `_` followed by the lower-case letter, which keeps them apart on
+/// case-insensitive filesystems.
+#[derive(Debug)]
+pub struct DiskCache {
+    dir: PathBuf,
+    config: DiskCacheConfig,
+}
+
+impl DiskCache {
+    /// Opens the cache in a directory with the default configuration, creating it if needed.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, HError> {
+        Self::with_config(dir, DiskCacheConfig::default())
+    }
+
+    /// Opens the cache in a directory with a configuration, creating it if needed.
+    pub fn with_config(dir: impl AsRef<Path>, config: DiskCacheConfig) -> Result<Self, HError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(dir.join("items"))?;
+        fs::create_dir_all(dir.join("users"))?;
+        Ok(Self { dir, config })
+    }
+
+    /// Returns the directory of this cache.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the configuration of this cache.
+    pub fn config(&self) -> &DiskCacheConfig {
+        &self.config
+    }
+
+    fn path(&self, key: &Key) -> Option<PathBuf> {
+        match key {
+            Key::Item(id) => Some(
+                self.dir
+                    .join("items")
+                    .join((id / BUCKET_SIZE).to_string())
+                    .join(format!("{}.json", id)),
+            ),
+            Key::User(name) if is_safe_name(name) => Some(
+                self.dir
+                    .join("users")
+                    .join(format!("{}.json", file_name(name))),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Returns the stored response if it can be served without revalidation.
+    pub(crate) fn get(&self, key: &Key) -> Option<String> {
+        let path = self.path(key)?;
+        let body = fs::read_to_string(&path).ok()?;
+        let now = SystemTime::now();
+        let fresh = match key {
+            Key::Item(_) => {
+                let item: Item = from_str(&body).ok()?;
+                match item.time {
+                    Some(time) => {
+                        let created = UNIX_EPOCH + Duration::from_secs(time);
+                        now.duration_since(created).unwrap_or_default()
+                            >= self.config.immutable_after
+                    }
+                    None => item.deleted.unwrap_or(false),
+                }
+            }
+            _ => {
+                let modified = fs::metadata(&path).and_then(|meta| meta.modified()).ok()?;
+                now.duration_since(modified).unwrap_or_default() < self.config.user_max_age
+            }
+        };
+        fresh.then_some(body)
+    }
+
+    pub(crate) fn insert(&self, key: &Key, body: &str) -> Result<(), HError> {
+        let Some(path) = self.path(key) else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // A unique name keeps concurrent writers of the same key from clobbering each other.
+        let count = TEMPORARY_COUNT.fetch_add(1, Ordering::Relaxed);
+        let temporary = path.with_extension(format!("json.{}.{}.tmp", process::id(), count));
+        fs::write(&temporary, body)?;
+        fs::rename(&temporary, &path).inspect_err(|_| {
+            let _ = fs::remove_file(&temporary);
+        })?;
+        Ok(())
+    }
+
+    pub(crate) fn contains(&self, key: &Key) -> bool {
+        self.path(key).is_some_and(|path| path.exists())
+    }
+
+    pub(crate) fn invalidate(&self, key: &Key) -> Result<(), HError> {
+        let Some(path) = self.path(key) else {
+            return Ok(());
+        };
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Removes the stored item with the id, if any.
+    pub fn invalidate_item(&self, id: u64) -> Result<(), HError> {
+        self.invalidate(&Key::Item(id))
+    }
+
+    /// Removes the stored user with the username, if any.
+    pub fn invalidate_user(&self, name: &str) -> Result<(), HError> {
+        self.invalidate(&Key::User(name.to_string()))
+    }
+}
+
+/// Escapes a username so that names differing only in case get different file names.
+fn file_name(name: &str) -> String {
+    let mut out = String::new();
+    for c in name.chars() {
+        match c {
+            '_' => out.push_str("__"),
+            c if c.is_ascii_uppercase() => {
+                out.push('_');
+                out.push(c.to_ascii_lowercase());
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn is_safe_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
@@ -0,0 +1,164 @@
+//! Offline data source answering a [Client] from a local dump of items and users.
+//!
+//! ```no_run
+//! use hnews::dump::Dump;
+//! use hnews::prelude::*;
+//!
+//! let client = Client::new().with_dump(Dump::load("items.jsonl")?);
+//! let story = client.get_story(8863)?;
+//! for comment in story.comments(&client)? {
+//!     println!("{}", comment.by(&client)?.karma);
+//! }
+//! # Ok::<(), HError>(())
+//! ```
+
+use crate::prelude::*;
+use miniserde::json::{from_str, to_string, Number, Value};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Raw item and user JSON held in memory, used through [Client::with_dump].
+///
+/// Records are told apart by their `id`, which is a number for items and a string
+/// for users. Anything missing is answered with `null`, as the API does, so it ends
+/// up as [HError::NotFound]. Story lists are empty unless set with [list](Dump::list).
+#[derive(Debug, Default, Clone)]
+pub struct Dump {
+    items: HashMap<u64, String>,
+    users: HashMap<String, String>,
+    lists: HashMap<&'static str, Vec<u64>>,
+    max_item: Option<u64>,
+}
+
+impl Dump {
+    /// Creates an empty dump.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a JSON Lines file with one item or user per line.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, HError> {
+        let mut dump = Self::new();
+        dump.read_lines(path.as_ref())?;
+        Ok(dump)
+    }
+
+    /// Loads every `.json` file, holding one record, and `.jsonl` file below a directory.
+    ///
+    /// This includes the layout of a [DiskCache](crate::disk_cache::DiskCache).
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Self, HError> {
+        let mut dump = Self::new();
+        dump.read_dir(dir.as_ref())?;
+        Ok(dump)
+    }
+
+    fn read_dir(&mut self, dir: &Path) -> Result<(), HError> {
+        let mut entries = fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+        entries.sort_by_key(|entry| entry.path());
+        for entry in entries {
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                self.read_dir(&path)?;
+            } else {
+                match path.extension().and_then(|ext| ext.to_str()) {
+                    Some("json") => self.insert_json(&fs::read_to_string(&path)?)?,
+                    Some("jsonl") => self.read_lines(&path)?,
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn read_lines(&mut self, path: &Path) -> Result<(), HError> {
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                self.insert_json(&line)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds an item or user from its raw JSON, replacing any with the same id.
+    ///
+    /// A `null` record is skipped.
+    pub fn insert_json(&mut self, json: &str) -> Result<(), HError> {
+        let value: Value = from_str(json)?;
+        let id = match &value {
+            Value::Null => return Ok(()),
+            Value::Object(object) => object.get("id"),
+            _ => None,
+        };
+        match id {
+            Some(Value::Number(Number::U64(id))) => self.insert_item(*id, json.trim().to_string()),
+            Some(Value::String(name)) => self.insert_user(name.clone(), json.trim().to_string()),
+            _ => return Err(HError::ConversionFailed),
+        }
+        Ok(())
+    }
+
+    pub(crate) fn insert_item(&mut self, id: u64, json: String) {
+        self.items.insert(id, json);
+    }
+
+    pub(crate) fn insert_user(&mut self, name: String, json: String) {
+        self.users.insert(name, json);
+    }
+
+    #[cfg(feature = "testing")]
+    pub(crate) fn item_json(&self, id: u64) -> Option<&str> {
+        self.items.get(&id).map(String::as_str)
+    }
+
+    /// Sets the ids of a story list, in ranked order.
+    pub fn list(mut self, list: StoryList, ids: &[u64]) -> Self {
+        self.lists.insert(list.name(), ids.to_vec());
+        self
+    }
+
+    /// Sets the latest item id, which defaults to the highest id in the dump.
+    pub fn max_item(mut self, id: u64) -> Self {
+        self.max_item = Some(id);
+        self
+    }
+
+    /// Returns the amount of items.
+    pub fn items(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns the amount of users.
+    pub fn users(&self) -> usize {
+        self.users.len()
+    }
+
+    /// Returns the JSON body for a path below the API version, or `null` if there is nothing.
+    ///
+    /// Nothing changes in a dump, so its updates are always empty.
+    pub(crate) fn respond(&self, path: &str) -> String {
+        let path = path.trim_start_matches('/');
+        let Some(name) = path.strip_suffix(".json") else {
+            return "null".to_string();
+        };
+        let body = if let Some(id) = name.strip_prefix("item/") {
+            id.parse().ok().and_then(|id| self.items.get(&id)).cloned()
+        } else if let Some(user) = name.strip_prefix("user/") {
+            self.users.get(user).cloned()
+        } else if name == "maxitem" {
+            let max = self.items.keys().max().copied().unwrap_or(0);
+            Some(self.max_item.unwrap_or(max).to_string())
+        } else if name == "updates" {
+            Some("{\"items\":[],\"profiles\":[]}".to_string())
+        } else if let Some(ids) = self.lists.get(name) {
+            Some(to_string(ids))
+        } else if name.ends_with("stories") {
+            Some("[]".to_string())
+        } else {
+            None
+        };
+        body.unwrap_or_else(|| "null".to_string())
+    }
+}
@@ -1,46 +1,72 @@
-const URL: &str = "https://hacker-news.firebaseio.com";
-const VERSION: &str = "v0";
+/// The Firebase API of Hacker News, including the version.
+pub(crate) const DEFAULT_BASE_URL: &str = "https://hacker-news.firebaseio.com/v0";
 
-pub(crate) struct Endpoint;
+#[derive(Debug, Clone)]
+pub(crate) struct Endpoint {
+    base: String,
+}
+
+impl Default for Endpoint {
+    fn default() -> Self {
+        Endpoint::new(DEFAULT_BASE_URL)
+    }
+}
 
 impl Endpoint {
-    fn build(arg: String) -> String {
-        format!("{}/{}/{}", URL, VERSION, arg)
+    pub(crate) fn new(base: &str) -> Self {
+        Self {
+            base: base.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub(crate) fn build(&self, arg: &str) -> String {
+        format!("{}/{}", self.base, arg)
+    }
+
+    /// Strips the base from a URL built by this endpoint, such as `item/1.json`.
+    pub(crate) fn relative<'a>(&self, url: &'a str) -> &'a str {
+        url.strip_prefix(self.base.as_str())
+            .map(|path| path.trim_start_matches('/'))
+            .unwrap_or(url)
+    }
+
+    pub(crate) fn item(&self, id: u64) -> String {
+        self.build(&format!("item/{}.json", id))
     }
 
-    pub(crate) fn item(id: u64) -> String {
-        Endpoint::build(format!("item/{}.json", id))
+    pub(crate) fn user(&self, name: &str) -> String {
+        self.build(&format!("user/{}.json", name))
     }
 
-    pub(crate) fn user(name: &str) -> String {
-        Endpoint::build(format!("user/{}.json", name))
+    pub(crate) fn maxitem(&self) -> String {
+        self.build("maxitem.json")
     }
 
-    pub(crate) fn maxitem() -> String {
-        Endpoint::build("maxitem.json".into())
+    pub(crate) fn topstories(&self) -> String {
+        self.build("topstories.json")
     }
 
-    pub(crate) fn topstories() -> String {
-        Endpoint::build("topstories.json".into())
+    pub(crate) fn newstories(&self) -> String {
+        self.build("newstories.json")
     }
 
-    pub(crate) fn newstories() -> String {
-        Endpoint::build("newstories.json".into())
+    pub(crate) fn beststories(&self) -> String {
+        self.build("beststories.json")
     }
 
-    pub(crate) fn beststories() -> String {
-        Endpoint::build("beststories.json".into())
+    pub(crate) fn askstories(&self) -> String {
+        self.build("askstories.json")
     }
 
-    pub(crate) fn askstories() -> String {
-        Endpoint::build("askstories.json".into())
+    pub(crate) fn showstories(&self) -> String {
+        self.build("showstories.json")
     }
 
-    pub(crate) fn showstories() -> String {
-        Endpoint::build("showstories.json".into())
+    pub(crate) fn jobstories(&self) -> String {
+        self.build("jobstories.json")
     }
 
-    pub(crate) fn updates() -> String {
-        Endpoint::build("updates.json".into())
+    pub(crate) fn updates(&self) -> String {
+        self.build("updates.json")
     }
 }
@@ -3,18 +3,28 @@
 use std::error::Error;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 
-#[allow(clippy::large_enum_variant)]
+#[non_exhaustive]
 #[derive(Debug)]
 /// The error enum representing all possible errors that can originate from this crate.
 pub enum HError {
     /// Error originating from the `ureq` crate.
-    UReq(ureq::Error),
+    UReq(Box<ureq::Error>),
     /// Error originating from the `miniserde` crate.
     Miniserde(miniserde::Error),
     /// Error originating from `std::io::Error`.
     Io(std::io::Error),
     /// Conversion between returned data and our representation failed.
     ConversionFailed,
+    /// The requested item or user does not exist.
+    NotFound,
+    /// An offline client, replaying a [Cassette](crate::cassette::Cassette) or reading a
+    /// [Dump](crate::dump::Dump), cannot answer the request for the path.
+    Unrecorded(String),
+    /// A CSV column was selected that the record does not have.
+    UnknownColumn(String),
+    /// Error originating from the `rusqlite` crate.
+    #[cfg(feature = "sqlite")]
+    Sqlite(rusqlite::Error),
 }
 
 impl Error for HError {}
@@ -30,13 +40,18 @@ impl Display for HError {
                 fmt,
                 "Conversion between returned data and our representation failed."
             ),
+            NotFound => write!(fmt, "The requested item or user does not exist."),
+            Unrecorded(path) => write!(fmt, "No recorded response for {}.", path),
+            UnknownColumn(column) => write!(fmt, "Unknown column {}.", column),
+            #[cfg(feature = "sqlite")]
+            Sqlite(e) => write!(fmt, "Sqlite Error: {}", e),
         }
     }
 }
 
 impl From<ureq::Error> for HError {
     fn from(err: ureq::Error) -> Self {
-        HError::UReq(err)
+        HError::UReq(Box::new(err))
     }
 }
 
@@ -52,6 +67,13 @@ impl From<std::io::Error> for HError {
     }
 }
 
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for HError {
+    fn from(err: rusqlite::Error) -> Self {
+        HError::Sqlite(err)
+    }
+}
+
 macro_rules! convert {
     ($e:expr) => {
         $e.ok_or(HError::ConversionFailed)?
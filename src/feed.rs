@@ -0,0 +1,266 @@
+//! RSS 2.0 and Atom feeds of stories and jobs.
+
+use crate::date::{rfc3339, rfc822};
+use crate::prelude::*;
+use crate::text::escape_html;
+
+/// A single entry of a [Feed].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The id of the item.
+    pub id: u64,
+    /// The title of the item.
+    pub title: String,
+    /// Where the entry links to, the discussion for items without a URL.
+    pub link: String,
+    /// The discussion page of the item.
+    pub comments: String,
+    /// The username of the author.
+    pub author: String,
+    /// When the item was posted, as a Unix timestamp.
+    pub time: u64,
+    /// The score of the item.
+    pub score: i64,
+    /// The total amount of comments, [None] for jobs.
+    pub descendants: Option<u64>,
+}
+
+impl Entry {
+    /// Creates an entry from a submission, or [None] for comments, poll options and deleted items.
+    pub fn from_any(item: &AnyItem) -> Option<Self> {
+        match item {
+            AnyItem::Story(story) => Some(story.into()),
+            AnyItem::Ask(ask) => Some(ask.into()),
+            AnyItem::Job(job) => Some(job.into()),
+            AnyItem::Poll(poll) => Some(poll.into()),
+            _ => None,
+        }
+    }
+
+    fn description(&self) -> String {
+        let mut description = format!("{} points by {}", self.score, self.author);
+        if let Some(descendants) = self.descendants {
+            description.push_str(&format!(" | {} comments", descendants));
+        }
+        description
+    }
+}
+
+impl From<&Story> for Entry {
+    fn from(story: &Story) -> Self {
+        Self {
+            id: story.id,
+            title: story.title.clone(),
+            link: story.url.clone(),
+            comments: story.permalink(),
+            author: story.by.clone(),
+            time: story.time,
+            score: story.score,
+            descendants: Some(story.comments),
+        }
+    }
+}
+
+impl From<&Ask> for Entry {
+    fn from(ask: &Ask) -> Self {
+        Self {
+            id: ask.id,
+            title: ask.title.clone(),
+            link: ask.permalink(),
+            comments: ask.permalink(),
+            author: ask.by.clone(),
+            time: ask.time,
+            score: ask.score,
+            descendants: Some(ask.comments),
+        }
+    }
+}
+
+impl From<&Job> for Entry {
+    fn from(job: &Job) -> Self {
+        let link = match &job.url {
+            Some(url) if !url.is_empty() => url.clone(),
+            _ => job.permalink(),
+        };
+        Self {
+            id: job.id,
+            title: job.title.clone(),
+            link,
+            comments: job.permalink(),
+            author: job.by.clone(),
+            time: job.time,
+            score: job.score,
+            descendants: None,
+        }
+    }
+}
+
+impl From<&Poll> for Entry {
+    fn from(poll: &Poll) -> Self {
+        Self {
+            id: poll.id,
+            title: poll.title.clone(),
+            link: poll.permalink(),
+            comments: poll.permalink(),
+            author: poll.by.clone(),
+            time: poll.time,
+            score: poll.score,
+            descendants: Some(poll.comments),
+        }
+    }
+}
+
+/// A feed of stories and jobs that can be rendered as RSS 2.0 or Atom.
+///
+/// Filters apply to every entry, whether it was added before or after them.
+#[derive(Debug, Clone)]
+pub struct Feed {
+    title: String,
+    link: String,
+    description: String,
+    min_score: Option<i64>,
+    min_comments: Option<u64>,
+    entries: Vec<Entry>,
+}
+
+impl Feed {
+    /// Creates an empty feed with a title, linking to the Hacker News website.
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            link: WebHost::default().base().to_string(),
+            description: title.to_string(),
+            min_score: None,
+            min_comments: None,
+            entries: vec![],
+        }
+    }
+
+    /// Creates a feed of the submissions on a [Page], titled after its list.
+    pub fn from_page(page: &Page) -> Self {
+        Self::new(&format!("Hacker News: {}", page.list.title())).items(&page.items)
+    }
+
+    /// Sets the link of the feed itself.
+    pub fn link(mut self, link: &str) -> Self {
+        self.link = link.to_string();
+        self
+    }
+
+    /// Sets the description of the feed, which defaults to the title.
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    /// Leaves out entries with a lower score.
+    pub fn min_score(mut self, score: i64) -> Self {
+        self.min_score = Some(score);
+        self
+    }
+
+    /// Leaves out entries with fewer comments, including all jobs.
+    pub fn min_comments(mut self, comments: u64) -> Self {
+        self.min_comments = Some(comments);
+        self
+    }
+
+    /// Adds a single entry.
+    pub fn entry(mut self, entry: Entry) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    /// Adds stories as entries.
+    pub fn stories(mut self, stories: &[Story]) -> Self {
+        self.entries.extend(stories.iter().map(Entry::from));
+        self
+    }
+
+    /// Adds jobs as entries.
+    pub fn jobs(mut self, jobs: &[Job]) -> Self {
+        self.entries.extend(jobs.iter().map(Entry::from));
+        self
+    }
+
+    /// Adds the submissions among the items as entries, skipping everything else.
+    pub fn items(mut self, items: &[AnyItem]) -> Self {
+        self.entries
+            .extend(items.iter().filter_map(Entry::from_any));
+        self
+    }
+
+    /// Returns the entries that pass the filters, in the order they were added.
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.iter().filter(|entry| {
+            self.min_score.is_none_or(|min| entry.score >= min)
+                && self
+                    .min_comments
+                    .is_none_or(|min| entry.descendants.unwrap_or(0) >= min)
+        })
+    }
+
+    /// Renders the feed as RSS 2.0.
+    pub fn to_rss(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<rss version=\"2.0\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n");
+        out.push_str("<channel>\n");
+        element(&mut out, "title", &self.title);
+        element(&mut out, "link", &self.link);
+        element(&mut out, "description", &self.description);
+        for entry in self.entries() {
+            out.push_str("<item>\n");
+            element(&mut out, "title", &entry.title);
+            element(&mut out, "link", &entry.link);
+            element(&mut out, "comments", &entry.comments);
+            element(&mut out, "dc:creator", &entry.author);
+            element(&mut out, "pubDate", &rfc822(entry.time));
+            out.push_str(&format!(
+                "<guid isPermaLink=\"true\">{}</guid>\n",
+                escape_html(&entry.comments)
+            ));
+            element(&mut out, "description", &entry.description());
+            out.push_str("</item>\n");
+        }
+        out.push_str("</channel>\n</rss>\n");
+        out
+    }
+
+    /// Renders the feed as Atom.
+    pub fn to_atom(&self) -> String {
+        let updated = self.entries().map(|entry| entry.time).max().unwrap_or(0);
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+        element(&mut out, "title", &self.title);
+        element(&mut out, "subtitle", &self.description);
+        out.push_str(&format!("<link href=\"{}\"/>\n", escape_html(&self.link)));
+        element(&mut out, "id", &self.link);
+        element(&mut out, "updated", &rfc3339(updated));
+        for entry in self.entries() {
+            out.push_str("<entry>\n");
+            element(&mut out, "title", &entry.title);
+            out.push_str(&format!(
+                "<link rel=\"alternate\" href=\"{}\"/>\n",
+                escape_html(&entry.link)
+            ));
+            out.push_str(&format!(
+                "<link rel=\"replies\" type=\"text/html\" href=\"{}\"/>\n",
+                escape_html(&entry.comments)
+            ));
+            element(&mut out, "id", &entry.comments);
+            out.push_str("<author>\n");
+            element(&mut out, "name", &entry.author);
+            out.push_str("</author>\n");
+            element(&mut out, "published", &rfc3339(entry.time));
+            element(&mut out, "updated", &rfc3339(entry.time));
+            element(&mut out, "summary", &entry.description());
+            out.push_str("</entry>\n");
+        }
+        out.push_str("</feed>\n");
+        out
+    }
+}
+
+fn element(out: &mut String, name: &str, text: &str) {
+    out.push_str(&format!("<{0}>{1}</{0}>\n", name, escape_html(text)));
+}
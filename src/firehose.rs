@@ -0,0 +1,130 @@
+//! Iterator over every newly created item.
+
+use crate::parallel::{self, DEFAULT_THREADS};
+use crate::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::thread;
+use std::time::Duration;
+
+/// Follows the latest item id and yields every item created after it, in id order.
+///
+/// Ids that are not yet available return `null` for a short while after they are created,
+/// those are retried before being given up on with [HError::NotFound], as are failed requests.
+/// Errors are paired with the id of the item that was being fetched or waited for.
+/// The iterator never ends on its own, it waits for new items instead.
+#[derive(Debug)]
+pub struct Firehose {
+    client: Client,
+    next: u64,
+    max: u64,
+    ready: BTreeMap<u64, Result<AnyItem, HError>>,
+    attempts: HashMap<u64, u32>,
+    interval: Duration,
+    retry_delay: Duration,
+    max_attempts: u32,
+    threads: usize,
+}
+
+impl Firehose {
+    /// Creates a firehose starting after the current latest item.
+    pub fn new(client: &Client) -> Result<Self, HError> {
+        let client = client.uncached();
+        let max = client.get_latest()?;
+        Ok(Self::starting_at(&client, max + 1))
+    }
+
+    /// Creates a firehose starting at the item with the id.
+    pub fn starting_at(client: &Client, id: u64) -> Self {
+        Self {
+            client: client.uncached(),
+            next: id,
+            max: id.saturating_sub(1),
+            ready: BTreeMap::new(),
+            attempts: HashMap::new(),
+            interval: Duration::from_secs(10),
+            retry_delay: Duration::from_secs(1),
+            max_attempts: 10,
+            threads: DEFAULT_THREADS,
+        }
+    }
+
+    /// Sets how long to wait before checking for new items again.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets how long to wait before fetching an item that returned `null` or failed again.
+    pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Sets how often an item returning `null` or failing is fetched before giving up on it.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets how many items are fetched concurrently.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Returns the id of the next item that will be yielded.
+    pub fn next_id(&self) -> u64 {
+        self.next
+    }
+
+    fn fetch_batch(&mut self) {
+        let end = self.max.min(self.next + self.threads as u64 * 4);
+        let ids: Vec<u64> = (self.next..=end)
+            .filter(|id| !self.ready.contains_key(id))
+            .collect();
+        let client = &self.client;
+        let results = parallel::map(&ids, self.threads, |id| client.get_any(*id));
+
+        for (id, result) in ids.into_iter().zip(results) {
+            if let Err(HError::NotFound | HError::UReq(_) | HError::Io(_)) = result {
+                let attempts = self.attempts.entry(id).or_insert(0);
+                *attempts += 1;
+                if *attempts < self.max_attempts {
+                    continue;
+                }
+            }
+            self.attempts.remove(&id);
+            self.ready.insert(id, result);
+        }
+    }
+}
+
+impl Iterator for Firehose {
+    type Item = Result<AnyItem, (u64, HError)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(result) = self.ready.remove(&self.next) {
+                let id = self.next;
+                self.next += 1;
+                return Some(result.map_err(|e| (id, e)));
+            }
+
+            if self.next > self.max {
+                match self.client.get_latest() {
+                    Ok(max) if max >= self.next => self.max = max,
+                    Ok(_) => {
+                        thread::sleep(self.interval);
+                        continue;
+                    }
+                    Err(e) => return Some(Err((self.next, e))),
+                }
+            }
+
+            self.fetch_batch();
+            if !self.ready.contains_key(&self.next) {
+                thread::sleep(self.retry_delay);
+            }
+        }
+    }
+}
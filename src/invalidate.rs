@@ -0,0 +1,65 @@
+//! Cache invalidation driven by the `updates` endpoint.
+
+use crate::prelude::*;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// What to do with cached entries that changed upstream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Invalidation {
+    /// Drop them, so the next request fetches them again.
+    Evict,
+    /// Drop them and immediately fetch the ones that were cached again.
+    Refresh,
+}
+
+/// The items and profiles invalidated by [Client::apply_updates].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Invalidated {
+    /// Ids of items that changed.
+    pub items: Vec<u64>,
+    /// Usernames of profiles that changed.
+    pub profiles: Vec<String>,
+}
+
+/// Applies [Client::apply_updates] on a background thread until dropped.
+///
+/// Failed rounds are skipped and retried on the next interval.
+#[derive(Debug)]
+pub struct Invalidator {
+    stop: Option<Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Invalidator {
+    /// Starts invalidating the caches of the client every `interval`.
+    pub fn spawn(client: Client, interval: Duration, mode: Invalidation) -> Self {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let handle = thread::spawn(move || loop {
+            let _ = client.apply_updates(mode);
+            match stopped.recv_timeout(interval) {
+                Err(RecvTimeoutError::Timeout) => continue,
+                _ => break,
+            }
+        });
+        Self {
+            stop: Some(stop),
+            handle: Some(handle),
+        }
+    }
+
+    /// Stops the background thread and waits for it to finish.
+    pub fn stop(self) {
+        drop(self);
+    }
+}
+
+impl Drop for Invalidator {
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
@@ -1,10 +1,13 @@
-#![doc(hidden)]
+//! Raw item as returned by the API and conversions into the typed structs.
 
 use crate::prelude::*;
 use miniserde::{Deserialize, Serialize};
 
 #[non_exhaustive]
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+/// An item exactly as returned by the API, with every field optional.
+///
+/// Convert it into [AnyItem] or one of the typed structs with [TryFrom].
 pub struct Item {
     pub(crate) id: u64,
     pub(crate) deleted: Option<bool>,
@@ -24,6 +27,28 @@ pub struct Item {
     pub(crate) descendants: Option<u64>,
 }
 
+impl Item {
+    /// Returns the unique id of this item.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the kind of this item, such as `story` or `comment`.
+    pub fn kind(&self) -> Option<&str> {
+        self.kind.as_deref()
+    }
+
+    /// Returns whether this item was deleted.
+    pub fn is_deleted(&self) -> bool {
+        self.deleted.unwrap_or(false)
+    }
+
+    /// Returns whether this item is dead, meaning flagged or killed by moderators.
+    pub fn is_dead(&self) -> bool {
+        self.dead.unwrap_or(false)
+    }
+}
+
 impl TryFrom<Item> for Ask {
     type Error = HError;
 
@@ -38,7 +63,7 @@ impl TryFrom<Item> for Ask {
             id: item.id,
             kids: convert_default!(item.kids),
             score: convert!(item.score),
-            text: convert!(item.text),
+            text: convert_default!(item.text),
             time: convert!(item.time),
             title: convert!(item.title),
         })
@@ -55,6 +80,7 @@ impl TryFrom<Item> for Comment {
         }
         Ok(Comment {
             by: convert!(item.by),
+            dead: convert_default!(item.dead),
             id: item.id,
             kids: convert_default!(item.kids),
             parent: convert!(item.parent),
@@ -76,9 +102,10 @@ impl TryFrom<Item> for Job {
             by: convert!(item.by),
             id: item.id,
             score: convert!(item.score),
+            text: item.text,
             time: convert!(item.time),
             title: convert!(item.title),
-            url: convert!(item.url),
+            url: item.url,
         })
     }
 }
@@ -145,6 +172,109 @@ impl TryFrom<Item> for Story {
     }
 }
 
+impl TryFrom<Item> for AnyItem {
+    type Error = HError;
+
+    fn try_from(item: Item) -> Result<Self, Self::Error> {
+        if item.is_deleted() {
+            return Ok(AnyItem::Deleted(item.id));
+        }
+        let kind = convert!(item.kind.as_deref());
+        Ok(match kind {
+            "story" if item.url.is_some() => AnyItem::Story(item.try_into()?),
+            "story" => AnyItem::Ask(item.try_into()?),
+            "job" => AnyItem::Job(item.try_into()?),
+            "poll" => AnyItem::Poll(item.try_into()?),
+            "pollopt" => AnyItem::PollOption(item.try_into()?),
+            "comment" => AnyItem::Comment(item.try_into()?),
+            _ => return Err(HError::ConversionFailed),
+        })
+    }
+}
+
+/// Turns a typed item back into its raw form, keeping every field the typed struct has.
+impl From<AnyItem> for Item {
+    fn from(item: AnyItem) -> Self {
+        let kind = |kind: &str| Some(kind.to_string());
+        match item {
+            AnyItem::Story(story) => Item {
+                id: story.id,
+                kind: kind("story"),
+                by: Some(story.by),
+                time: Some(story.time),
+                kids: Some(story.kids),
+                url: Some(story.url),
+                score: Some(story.score),
+                title: Some(story.title),
+                descendants: Some(story.comments),
+                ..Default::default()
+            },
+            AnyItem::Ask(ask) => Item {
+                id: ask.id,
+                kind: kind("story"),
+                by: Some(ask.by),
+                time: Some(ask.time),
+                text: Some(ask.text),
+                kids: Some(ask.kids),
+                score: Some(ask.score),
+                title: Some(ask.title),
+                descendants: Some(ask.comments),
+                ..Default::default()
+            },
+            AnyItem::Job(job) => Item {
+                id: job.id,
+                kind: kind("job"),
+                by: Some(job.by),
+                time: Some(job.time),
+                text: job.text,
+                url: job.url,
+                score: Some(job.score),
+                title: Some(job.title),
+                ..Default::default()
+            },
+            AnyItem::Poll(poll) => Item {
+                id: poll.id,
+                kind: kind("poll"),
+                by: Some(poll.by),
+                time: Some(poll.time),
+                text: Some(poll.text),
+                kids: Some(poll.kids),
+                score: Some(poll.score),
+                title: Some(poll.title),
+                parts: Some(poll.parts),
+                descendants: Some(poll.comments),
+                ..Default::default()
+            },
+            AnyItem::PollOption(option) => Item {
+                id: option.id,
+                kind: kind("pollopt"),
+                by: Some(option.by),
+                time: Some(option.time),
+                text: Some(option.text),
+                poll: Some(option.poll),
+                score: Some(option.score),
+                ..Default::default()
+            },
+            AnyItem::Comment(comment) => Item {
+                id: comment.id,
+                kind: kind("comment"),
+                by: Some(comment.by),
+                time: Some(comment.time),
+                text: Some(comment.text),
+                dead: comment.dead.then_some(true),
+                parent: Some(comment.parent),
+                kids: Some(comment.kids),
+                ..Default::default()
+            },
+            AnyItem::Deleted(id) => Item {
+                id,
+                deleted: Some(true),
+                ..Default::default()
+            },
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct Update {
     pub(crate) items: Vec<u64>,
@@ -1,6 +1,7 @@
 //! Job struct and associated methods.
 
 use crate::prelude::*;
+use crate::site;
 
 #[non_exhaustive]
 #[derive(Debug)]
@@ -14,10 +15,12 @@ pub struct Job {
     pub score: i64,
     /// When this submission was made, as a Unix timestamp.
     pub time: u64,
+    /// The text of this submission, if it has one.
+    pub text: Option<String>,
     /// The title of this submission.
     pub title: String,
-    /// The URL where this submission leads.
-    pub url: String,
+    /// The URL where this submission leads, if it has one.
+    pub url: Option<String>,
 }
 
 impl Job {
@@ -25,4 +28,19 @@ impl Job {
     pub fn by(&self, client: &Client) -> Result<User, HError> {
         client.get_user(&self.by)
     }
+
+    /// Returns the domain this job posting links to, if it has a URL.
+    pub fn domain(&self) -> Option<String> {
+        self.url.as_deref().and_then(site::domain)
+    }
+
+    /// Returns the page of this job posting on the Hacker News website.
+    pub fn permalink(&self) -> String {
+        self.permalink_on(&WebHost::default())
+    }
+
+    /// Returns the page of this job posting on a different web host.
+    pub fn permalink_on(&self, host: &WebHost) -> String {
+        host.item(self.id)
+    }
 }
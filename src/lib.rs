@@ -16,24 +16,54 @@
 //! [crate-link]: https://crates.io/crates/hnews
 //! [crate-version]: https://img.shields.io/crates/v/hnews.svg?style=flat-square
 
+pub mod any_item;
 pub mod ask;
+#[cfg(feature = "testing")]
+pub mod builder;
+pub mod cache;
+pub mod cassette;
 pub mod client;
 pub mod comment;
+pub mod crawler;
+pub mod csv;
+pub(crate) mod date;
+pub mod disk_cache;
+pub mod dump;
 pub(crate) mod endpoint;
 #[macro_use]
 pub mod error;
+pub mod feed;
+pub mod firehose;
+pub mod invalidate;
 pub mod item;
 pub mod job;
+pub mod list;
+pub(crate) mod parallel;
+pub mod permalink;
 pub mod poll;
 pub mod prelude;
+pub mod render;
+pub mod search;
+pub mod sink;
+pub mod site;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
 pub mod story;
+pub mod stream;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod text;
+pub mod thread;
 pub mod user;
+pub mod watch;
 
+pub use any_item::AnyItem;
 pub use ask::Ask;
 pub use client::Client;
 pub use comment::Comment;
 pub use error::HError;
+pub use item::Item;
 pub use job::Job;
-pub use poll::{Poll, PollOption};
+pub use poll::{Poll, PollOption, PollResults};
 pub use story::Story;
 pub use user::User;
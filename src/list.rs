@@ -0,0 +1,71 @@
+//! The ranked story lists of Hacker News and pages of them.
+
+use crate::prelude::*;
+
+/// Amount of items on a page of a list, the same as on the website.
+pub const PAGE_SIZE: usize = 30;
+
+/// One of the story lists published by the API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StoryList {
+    /// The front page ranking.
+    Top,
+    /// The newest submissions.
+    New,
+    /// The highest voted recent submissions.
+    Best,
+    /// The latest Ask HN posts.
+    Ask,
+    /// The latest Show HN posts.
+    Show,
+    /// The latest job postings.
+    Job,
+}
+
+impl StoryList {
+    /// Returns the name of the list in the API, such as `topstories`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            StoryList::Top => "topstories",
+            StoryList::New => "newstories",
+            StoryList::Best => "beststories",
+            StoryList::Ask => "askstories",
+            StoryList::Show => "showstories",
+            StoryList::Job => "jobstories",
+        }
+    }
+
+    /// Returns a human readable title of the list, such as `Top stories`.
+    pub fn title(&self) -> &'static str {
+        match self {
+            StoryList::Top => "Top stories",
+            StoryList::New => "New stories",
+            StoryList::Best => "Best stories",
+            StoryList::Ask => "Ask HN",
+            StoryList::Show => "Show HN",
+            StoryList::Job => "Jobs",
+        }
+    }
+
+    pub(crate) fn url(&self, endpoint: &Endpoint) -> String {
+        match self {
+            StoryList::Top => endpoint.topstories(),
+            StoryList::New => endpoint.newstories(),
+            StoryList::Best => endpoint.beststories(),
+            StoryList::Ask => endpoint.askstories(),
+            StoryList::Show => endpoint.showstories(),
+            StoryList::Job => endpoint.jobstories(),
+        }
+    }
+}
+
+/// A page of a [StoryList], created by [Client::get_page].
+#[derive(Debug)]
+pub struct Page {
+    /// The list the page belongs to.
+    pub list: StoryList,
+    /// The number of the page, starting at 1.
+    pub number: usize,
+    /// The items on the page, in ranked order.
+    pub items: Vec<AnyItem>,
+}
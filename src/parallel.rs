@@ -0,0 +1,40 @@
+//! Concurrent fetching on scoped threads.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// The amount of threads used when fetching many items at once.
+pub(crate) const DEFAULT_THREADS: usize = 8;
+
+/// Applies `f` to every input on up to `threads` threads, keeping the input order.
+pub(crate) fn map<T, U, F>(inputs: &[T], threads: usize, f: F) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> U + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<U>>> = Mutex::new(inputs.iter().map(|_| None).collect());
+    let threads = threads.clamp(1, inputs.len().max(1));
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(input) = inputs.get(index) else {
+                    break;
+                };
+                let output = f(input);
+                results.lock().unwrap()[index] = Some(output);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|output| output.expect("every input is processed"))
+        .collect()
+}
@@ -0,0 +1,141 @@
+//! Links to the Hacker News website and parsing them back into references.
+
+use crate::prelude::*;
+use crate::site::{decode_query, encode_query, Url};
+
+/// The website of Hacker News itself.
+pub const DEFAULT_WEB_HOST: &str = "https://news.ycombinator.com";
+
+/// The website permalinks are built for, configurable for self-hosted Hacker News clones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebHost {
+    base: String,
+}
+
+impl Default for WebHost {
+    fn default() -> Self {
+        WebHost::new(DEFAULT_WEB_HOST)
+    }
+}
+
+impl WebHost {
+    /// Creates a web host from its base URL, such as `https://news.ycombinator.com`.
+    pub fn new(base: &str) -> Self {
+        Self {
+            base: base.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Returns the base URL of this web host.
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    /// Returns the page of an item with the id.
+    pub fn item(&self, id: u64) -> String {
+        format!("{}/item?id={}", self.base, id)
+    }
+
+    /// Returns the page of an item scrolled to one of the comments below it.
+    pub fn comment(&self, item: u64, comment: u64) -> String {
+        format!("{}/item?id={}#{}", self.base, item, comment)
+    }
+
+    /// Returns the profile page of a [User], with the name percent-encoded.
+    pub fn user(&self, name: &str) -> String {
+        format!("{}/user?id={}", self.base, encode_query(name, ""))
+    }
+
+    /// Returns the page listing the submissions of a [User], with the name percent-encoded.
+    pub fn submitted(&self, name: &str) -> String {
+        format!("{}/submitted?id={}", self.base, encode_query(name, ""))
+    }
+
+    /// Returns the page listing submissions from a site, such as `github.com/user`.
+    ///
+    /// The site is percent-encoded, except for `/` and `:`.
+    pub fn from_site(&self, site: &str) -> String {
+        format!("{}/from?site={}", self.base, encode_query(site, "/:"))
+    }
+
+    /// Parses a URL on this web host back into a typed reference.
+    ///
+    /// The scheme and a leading `www.` are ignored when matching the host.
+    /// Returns [None] for URLs on other hosts or pages without a reference.
+    pub fn parse(&self, url: &str) -> Option<WebRef> {
+        let (url, fragment) = match url.trim().split_once('#') {
+            Some((url, fragment)) => (url, Some(fragment)),
+            None => (url.trim(), None),
+        };
+        let parsed = Url::parse(url)?;
+        let base = Url::parse(&self.base)?;
+        let strip = |host: &str| host.strip_prefix("www.").unwrap_or(host).to_string();
+        if strip(&parsed.host) != strip(&base.host) || parsed.port != base.port {
+            return None;
+        }
+
+        let page = parsed.path.strip_prefix(base.path)?.trim_start_matches('/');
+        let param = |name: &str| {
+            parsed
+                .query?
+                .split('&')
+                .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+                .filter(|value| !value.is_empty())
+                .map(decode_query)
+        };
+
+        match page {
+            "item" => Some(WebRef::Item(ItemRef {
+                id: param("id")?.parse().ok()?,
+                comment: fragment.and_then(|fragment| fragment.parse().ok()),
+            })),
+            "user" => Some(WebRef::User(UserRef::new(&param("id")?))),
+            "submitted" => Some(WebRef::Submitted(UserRef::new(&param("id")?))),
+            "from" => Some(WebRef::Site(param("site")?)),
+            _ => None,
+        }
+    }
+}
+
+/// A page on the Hacker News website.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebRef {
+    /// The page of an item.
+    Item(ItemRef),
+    /// The profile page of a user.
+    User(UserRef),
+    /// The submissions page of a user.
+    Submitted(UserRef),
+    /// The submissions from a site.
+    Site(String),
+}
+
+/// A reference to an item, such as a [Story] or a [Comment].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemRef {
+    /// The id of the item.
+    pub id: u64,
+    /// The comment the page was scrolled to, if any.
+    pub comment: Option<u64>,
+}
+
+/// A reference to a [User].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserRef {
+    /// The username of the user.
+    pub name: String,
+}
+
+impl UserRef {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+        }
+    }
+
+    /// Returns the referenced [User].
+    pub fn get(&self, client: &Client) -> Result<User, HError> {
+        client.get_user(&self.name)
+    }
+}
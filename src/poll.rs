@@ -1,5 +1,6 @@
 //! Poll struct and associated methods.
 
+use crate::parallel::{self, DEFAULT_THREADS};
 use crate::prelude::*;
 
 #[non_exhaustive]
@@ -45,6 +46,46 @@ impl Poll {
             .map(|kid| client.get_poll_option(*kid))
             .collect()
     }
+
+    /// Returns the text of this poll decoded to plain text.
+    pub fn text_plain(&self) -> String {
+        Document::parse(&self.text).to_plain()
+    }
+
+    /// Returns the text of this poll converted to Markdown.
+    pub fn text_markdown(&self) -> String {
+        Document::parse(&self.text).to_markdown()
+    }
+
+    /// Returns the text of this poll as sanitized HTML.
+    pub fn text_html(&self) -> String {
+        Document::parse(&self.text).to_html()
+    }
+
+    /// Returns the links referenced in the text of this poll.
+    pub fn links(&self) -> Vec<Link> {
+        Document::parse(&self.text).links()
+    }
+
+    /// Returns the [results](PollResults) of this poll, fetching all options concurrently.
+    pub fn results(&self, client: &Client) -> Result<PollResults, HError> {
+        let options = parallel::map(&self.parts, DEFAULT_THREADS, |id| {
+            client.get_poll_option(*id)
+        })
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+        Ok(PollResults::new(options))
+    }
+
+    /// Returns the page of this poll on the Hacker News website.
+    pub fn permalink(&self) -> String {
+        self.permalink_on(&WebHost::default())
+    }
+
+    /// Returns the page of this poll on a different web host.
+    pub fn permalink_on(&self, host: &WebHost) -> String {
+        host.item(self.id)
+    }
 }
 
 #[non_exhaustive]
@@ -76,3 +117,105 @@ impl PollOption {
         client.get_poll(self.poll)
     }
 }
+
+#[derive(Debug)]
+/// The results of a [Poll], with vote shares and ranks computed.
+pub struct PollResults {
+    /// The total score of all options.
+    pub total: u64,
+    /// The results of each option, in the order the poll lists them.
+    pub options: Vec<OptionResult>,
+}
+
+#[derive(Debug)]
+/// The result of a single [Poll Option](PollOption).
+pub struct OptionResult {
+    /// The poll option itself.
+    pub option: PollOption,
+    /// The share of the total score, between 0 and 1.
+    pub share: f64,
+    /// The rank of this option, starting at 1. Options with equal scores share a rank.
+    pub rank: usize,
+}
+
+impl PollResults {
+    /// Computes the results from a list of poll options.
+    pub fn new(options: Vec<PollOption>) -> Self {
+        let votes = |option: &PollOption| option.score.max(0) as u64;
+        let total: u64 = options.iter().map(votes).sum();
+        let scores: Vec<u64> = options.iter().map(votes).collect();
+        let options = options
+            .into_iter()
+            .map(|option| {
+                let score = votes(&option);
+                OptionResult {
+                    share: if total == 0 {
+                        0.0
+                    } else {
+                        score as f64 / total as f64
+                    },
+                    rank: 1 + scores.iter().filter(|other| **other > score).count(),
+                    option,
+                }
+            })
+            .collect();
+        Self { total, options }
+    }
+
+    /// Returns the option with the highest score, or [None] if nobody voted yet.
+    ///
+    /// On a tie the option listed first wins.
+    pub fn leader(&self) -> Option<&OptionResult> {
+        self.ranked()
+            .into_iter()
+            .next()
+            .filter(|result| result.option.score > 0)
+    }
+
+    /// Returns the results ordered by rank.
+    pub fn ranked(&self) -> Vec<&OptionResult> {
+        let mut ranked: Vec<&OptionResult> = self.options.iter().collect();
+        ranked.sort_by_key(|result| result.rank);
+        ranked
+    }
+
+    /// Renders the results as a plain-text bar chart, with bars up to `width` characters long.
+    pub fn render_bars(&self, width: usize) -> String {
+        let labels: Vec<String> = self
+            .options
+            .iter()
+            .map(|result| {
+                let label = Document::parse(&result.option.text).to_plain();
+                match label.char_indices().nth(40) {
+                    Some((end, _)) => format!("{}…", &label[..end]),
+                    None => label,
+                }
+            })
+            .collect();
+        let label_width = labels
+            .iter()
+            .map(|label| label.chars().count())
+            .max()
+            .unwrap_or(0);
+        let score_width = self
+            .options
+            .iter()
+            .map(|result| result.option.score.to_string().len())
+            .max()
+            .unwrap_or(0);
+
+        let mut out = String::new();
+        for (result, label) in self.options.iter().zip(labels) {
+            let filled = (result.share * width as f64).round() as usize;
+            out.push_str(&format!(
+                "{:<label_width$} {}{} {:>score_width$} ({:>5.1}%)\n",
+                label,
+                "█".repeat(filled),
+                " ".repeat(width - filled),
+                result.option.score,
+                result.share * 100.0,
+            ));
+        }
+        out
+    }
+}
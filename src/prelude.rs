@@ -1,14 +1,19 @@
 //! Useful re-exports for this crate.
 
+pub use crate::any_item::AnyItem;
 pub use crate::ask::Ask;
 pub use crate::client::Client;
 pub use crate::comment::Comment;
 pub(crate) use crate::endpoint::Endpoint;
 pub use crate::error::HError;
-pub(crate) use crate::item::Item;
+pub use crate::item::Item;
 pub(crate) use crate::item::Update;
 pub use crate::job::Job;
-pub use crate::poll::{Poll, PollOption};
+pub use crate::list::{Page, StoryList};
+pub use crate::permalink::WebHost;
+pub use crate::poll::{Poll, PollOption, PollResults};
 pub use crate::story::Story;
+pub use crate::text::{Document, Link};
+pub use crate::thread::{CommentNode, Thread};
 pub use crate::user::User;
 pub(crate) use std::convert::{TryFrom, TryInto};
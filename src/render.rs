@@ -0,0 +1,279 @@
+//! Rendering of whole [Threads](Thread) as Markdown, HTML and plain text.
+
+use crate::date;
+use crate::prelude::*;
+use crate::text::{escape_html, escape_markdown};
+
+/// The parts of the root item shown above the comments.
+struct Header {
+    title: String,
+    url: Option<String>,
+    by: Option<String>,
+    time: Option<u64>,
+    score: Option<i64>,
+    comments: Option<u64>,
+    text: Option<String>,
+    permalink: String,
+}
+
+impl Header {
+    fn new(root: &AnyItem, host: &WebHost) -> Self {
+        let (title, url, score, comments, text) = match root {
+            AnyItem::Story(story) => (
+                story.title.clone(),
+                Some(story.url.clone()),
+                Some(story.score),
+                Some(story.comments),
+                None,
+            ),
+            AnyItem::Ask(ask) => (
+                ask.title.clone(),
+                None,
+                Some(ask.score),
+                Some(ask.comments),
+                Some(ask.text.clone()),
+            ),
+            AnyItem::Job(job) => (
+                job.title.clone(),
+                job.url.clone().filter(|url| !url.is_empty()),
+                Some(job.score),
+                None,
+                None,
+            ),
+            AnyItem::Poll(poll) => (
+                poll.title.clone(),
+                None,
+                Some(poll.score),
+                Some(poll.comments),
+                Some(poll.text.clone()),
+            ),
+            AnyItem::PollOption(option) => (
+                format!("Poll option {}", option.id),
+                None,
+                Some(option.score),
+                None,
+                Some(option.text.clone()),
+            ),
+            AnyItem::Comment(comment) => (
+                format!("Comment by {}", comment.by),
+                None,
+                None,
+                None,
+                Some(comment.text.clone()),
+            ),
+            AnyItem::Deleted(id) => (format!("[deleted] {}", id), None, None, None, None),
+        };
+        Self {
+            title,
+            url,
+            by: root.by().map(str::to_string),
+            time: root.time(),
+            score,
+            comments,
+            text: text.filter(|text| !text.is_empty()),
+            permalink: host.item(root.id()),
+        }
+    }
+
+    /// Returns a line such as `111 points by dhouston | 2007-04-04 19:16 UTC | 71 comments`.
+    fn byline(&self) -> String {
+        let mut parts = vec![];
+        match (self.score, &self.by) {
+            (Some(score), Some(by)) => parts.push(format!("{} points by {}", score, by)),
+            (None, Some(by)) => parts.push(format!("by {}", by)),
+            _ => {}
+        }
+        if let Some(time) = self.time {
+            parts.push(date::display(time));
+        }
+        if let Some(comments) = self.comments {
+            parts.push(format!("{} comments", comments));
+        }
+        parts.join(" | ")
+    }
+}
+
+fn marker(comment: &Comment) -> &'static str {
+    if comment.dead {
+        " [dead]"
+    } else {
+        ""
+    }
+}
+
+impl Thread {
+    /// Renders the thread as Markdown, with replies nested in block quotes.
+    pub fn to_markdown(&self) -> String {
+        self.to_markdown_on(&WebHost::default())
+    }
+
+    /// Renders the thread as Markdown, linking to a different web host.
+    pub fn to_markdown_on(&self, host: &WebHost) -> String {
+        let header = Header::new(&self.root, host);
+        let mut out = format!(
+            "# [{}]({})\n\n",
+            escape_markdown(&header.title),
+            header.permalink
+        );
+        if let Some(url) = &header.url {
+            out.push_str(&format!("<{}>\n\n", url));
+        }
+        out.push_str(&header.byline());
+        out.push_str("\n\n");
+        if let Some(text) = &header.text {
+            out.push_str(&Document::parse(text).to_markdown());
+            out.push_str("\n\n");
+        }
+        out.push_str("---\n");
+        for node in &self.comments {
+            out.push('\n');
+            out.push_str(&quote(&markdown_node(node, host)));
+        }
+        out
+    }
+
+    /// Renders the thread as a standalone HTML page, with collapsible replies.
+    pub fn to_html(&self) -> String {
+        self.to_html_on(&WebHost::default())
+    }
+
+    /// Renders the thread as a standalone HTML page, linking to a different web host.
+    pub fn to_html_on(&self, host: &WebHost) -> String {
+        let header = Header::new(&self.root, host);
+        let title = escape_html(&header.title);
+        let mut out = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        out.push_str(&format!("<title>{}</title>\n", title));
+        out.push_str(
+            "<style>ul.comments { list-style: none; padding-left: 1.5em; } \
+             .deleted, .dead { color: #888; }</style>\n",
+        );
+        out.push_str("</head>\n<body>\n");
+        out.push_str(&format!(
+            "<h1><a href=\"{}\">{}</a></h1>\n",
+            escape_html(&header.permalink),
+            title
+        ));
+        if let Some(url) = &header.url {
+            let url = escape_html(url);
+            out.push_str(&format!("<p><a href=\"{0}\">{0}</a></p>\n", url));
+        }
+        out.push_str(&format!("<p>{}</p>\n", escape_html(&header.byline())));
+        if let Some(text) = &header.text {
+            out.push_str(&Document::parse(text).to_html());
+            out.push('\n');
+        }
+        html_nodes(&self.comments, host, &mut out);
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    /// Renders the thread as plain text, indenting replies with ASCII bars.
+    pub fn to_plain(&self) -> String {
+        let header = Header::new(&self.root, &WebHost::default());
+        let mut out = format!("{}\n", header.title);
+        if let Some(url) = &header.url {
+            out.push_str(&format!("{}\n", url));
+        }
+        out.push_str(&header.byline());
+        out.push('\n');
+        if let Some(text) = &header.text {
+            out.push('\n');
+            out.push_str(&Document::parse(text).to_plain());
+            out.push('\n');
+        }
+        for (depth, node) in self.walk() {
+            let indent = "|   ".repeat(depth);
+            out.push_str(indent.trim_end());
+            out.push('\n');
+            match &node.comment {
+                None => out.push_str(&format!("{}[deleted]\n", indent)),
+                Some(comment) => {
+                    out.push_str(&format!(
+                        "{}{}{} - {}\n",
+                        indent,
+                        comment.by,
+                        marker(comment),
+                        date::display(comment.time)
+                    ));
+                    for line in Document::parse(&comment.text).to_plain().lines() {
+                        let line = format!("{}{}", indent, line);
+                        out.push_str(line.trim_end());
+                        out.push('\n');
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Renders a comment and its replies, before quoting.
+fn markdown_node(node: &CommentNode, host: &WebHost) -> String {
+    let mut out = match &node.comment {
+        None => String::from("*[deleted]*"),
+        Some(comment) => format!(
+            "**{}**{} · [{}]({})\n\n{}",
+            comment.by,
+            marker(comment),
+            date::display(comment.time),
+            comment.permalink_on(host),
+            Document::parse(&comment.text).to_markdown()
+        ),
+    };
+    for child in &node.children {
+        out.push_str("\n\n");
+        out.push_str(&quote(&markdown_node(child, host)));
+    }
+    out
+}
+
+fn quote(markdown: &str) -> String {
+    let mut out = String::new();
+    for line in markdown.trim_end().lines() {
+        out.push('>');
+        if !line.is_empty() {
+            out.push(' ');
+            out.push_str(line);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn html_nodes(nodes: &[CommentNode], host: &WebHost, out: &mut String) {
+    if nodes.is_empty() {
+        return;
+    }
+    out.push_str("<ul class=\"comments\">\n");
+    for node in nodes {
+        let permalink = escape_html(&host.item(node.id));
+        match &node.comment {
+            None => {
+                out.push_str("<li class=\"deleted\"><details open><summary>");
+                out.push_str(&format!("<a href=\"{}\">[deleted]</a>", permalink));
+                out.push_str("</summary>\n");
+            }
+            Some(comment) => {
+                let class = if comment.dead {
+                    "comment dead"
+                } else {
+                    "comment"
+                };
+                out.push_str(&format!("<li class=\"{}\"><details open><summary>", class));
+                out.push_str(&format!(
+                    "<b>{}</b>{} <a href=\"{}\">{}</a>",
+                    escape_html(&comment.by),
+                    marker(comment),
+                    permalink,
+                    date::display(comment.time)
+                ));
+                out.push_str("</summary>\n");
+                out.push_str(&Document::parse(&comment.text).to_html());
+                out.push('\n');
+            }
+        }
+        html_nodes(&node.children, host, out);
+        out.push_str("</details></li>\n");
+    }
+    out.push_str("</ul>\n");
+}
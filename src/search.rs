@@ -0,0 +1,432 @@
+//! Full-text search through the Algolia API of Hacker News.
+//!
+//! Algolia also serves whole threads in one request, see [Client::get_algolia_thread].
+//!
+//! ```no_run
+//! use hnews::prelude::*;
+//! use hnews::search::{Search, Tag};
+//!
+//! let client = Client::new();
+//! let search = Search::new("rust").by_date().tag(Tag::Story).since(1_700_000_000);
+//! for hit in client.search(&search)?.hits {
+//!     println!("{:?}", hit.item.by());
+//! }
+//! # Ok::<(), HError>(())
+//! ```
+
+use crate::prelude::*;
+use crate::site::encode_query;
+use crate::thread;
+use miniserde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The Algolia API of Hacker News, including the version.
+pub(crate) const DEFAULT_SEARCH_URL: &str = "https://hn.algolia.com/api/v1";
+
+/// The item kinds Algolia tags hits with.
+const KINDS: [&str; 5] = ["story", "comment", "poll", "pollopt", "job"];
+
+/// A tag narrowing down a [Search].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Tag {
+    /// Stories, including Ask HN and Show HN.
+    Story,
+    /// Comments.
+    Comment,
+    /// Polls.
+    Poll,
+    /// Poll options.
+    PollOption,
+    /// Ask HN posts.
+    AskHn,
+    /// Show HN posts.
+    ShowHn,
+    /// Items currently on the front page.
+    FrontPage,
+    /// Items posted by the user.
+    Author(String),
+    /// Items belonging to the story with the id, including itself.
+    StoryId(u64),
+}
+
+impl Tag {
+    /// Returns the tag as Algolia spells it, such as `author_pg`.
+    pub fn name(&self) -> String {
+        match self {
+            Tag::Story => "story".to_string(),
+            Tag::Comment => "comment".to_string(),
+            Tag::Poll => "poll".to_string(),
+            Tag::PollOption => "pollopt".to_string(),
+            Tag::AskHn => "ask_hn".to_string(),
+            Tag::ShowHn => "show_hn".to_string(),
+            Tag::FrontPage => "front_page".to_string(),
+            Tag::Author(name) => format!("author_{}", name),
+            Tag::StoryId(id) => format!("story_{}", id),
+        }
+    }
+}
+
+/// A numeric attribute a [Search] can be filtered on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    /// When the item was posted, as a Unix timestamp.
+    CreatedAt,
+    /// The score.
+    Points,
+    /// The total amount of comments.
+    Comments,
+}
+
+impl Field {
+    fn name(&self) -> &'static str {
+        match self {
+            Field::CreatedAt => "created_at_i",
+            Field::Points => "points",
+            Field::Comments => "num_comments",
+        }
+    }
+}
+
+/// How a [Field] is compared in a numeric filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    /// `<`
+    Less,
+    /// `<=`
+    LessOrEqual,
+    /// `=`
+    Equal,
+    /// `>=`
+    GreaterOrEqual,
+    /// `>`
+    Greater,
+}
+
+impl Comparison {
+    fn operator(&self) -> &'static str {
+        match self {
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+            Comparison::Equal => "=",
+            Comparison::GreaterOrEqual => ">=",
+            Comparison::Greater => ">",
+        }
+    }
+}
+
+/// A search query, run with [Client::search].
+///
+/// Tags and filters added separately must all match. Results are ranked by relevance
+/// unless [by_date](Search::by_date) is used.
+#[derive(Debug, Clone, Default)]
+pub struct Search {
+    query: String,
+    by_date: bool,
+    tags: Vec<Vec<Tag>>,
+    filters: Vec<String>,
+    page: usize,
+    hits_per_page: Option<usize>,
+}
+
+impl Search {
+    /// Starts a search for the text, which may be empty to only filter.
+    pub fn new(query: &str) -> Self {
+        Self {
+            query: query.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Sorts the results by date, newest first.
+    pub fn by_date(mut self) -> Self {
+        self.by_date = true;
+        self
+    }
+
+    /// Only returns items with the tag.
+    pub fn tag(mut self, tag: Tag) -> Self {
+        self.tags.push(vec![tag]);
+        self
+    }
+
+    /// Only returns items with at least one of the tags.
+    pub fn any_tag(mut self, tags: &[Tag]) -> Self {
+        self.tags.push(tags.to_vec());
+        self
+    }
+
+    /// Only returns items whose field compares to the value.
+    pub fn filter(mut self, field: Field, comparison: Comparison, value: i64) -> Self {
+        self.filters.push(format!(
+            "{}{}{}",
+            field.name(),
+            comparison.operator(),
+            value
+        ));
+        self
+    }
+
+    /// Only returns items posted at or after the Unix timestamp.
+    pub fn since(self, time: u64) -> Self {
+        self.filter(Field::CreatedAt, Comparison::GreaterOrEqual, time as i64)
+    }
+
+    /// Only returns items posted before the Unix timestamp.
+    pub fn until(self, time: u64) -> Self {
+        self.filter(Field::CreatedAt, Comparison::Less, time as i64)
+    }
+
+    /// Only returns items with at least this score.
+    pub fn min_points(self, points: i64) -> Self {
+        self.filter(Field::Points, Comparison::GreaterOrEqual, points)
+    }
+
+    /// Only returns items with at least this many comments.
+    pub fn min_comments(self, comments: u64) -> Self {
+        self.filter(Field::Comments, Comparison::GreaterOrEqual, comments as i64)
+    }
+
+    /// Returns the page of results with the number, starting at 0.
+    pub fn page(mut self, page: usize) -> Self {
+        self.page = page;
+        self
+    }
+
+    /// Sets how many results a page holds, 20 unless set.
+    pub fn hits_per_page(mut self, hits: usize) -> Self {
+        self.hits_per_page = Some(hits);
+        self
+    }
+
+    pub(crate) fn url(&self, endpoint: &Endpoint) -> String {
+        let mut params = vec![format!("query={}", encode_query(&self.query, ""))];
+        if !self.tags.is_empty() {
+            let tags: Vec<String> = self
+                .tags
+                .iter()
+                .map(|group| {
+                    let names: Vec<String> = group.iter().map(Tag::name).collect();
+                    match names.len() {
+                        1 => names[0].clone(),
+                        _ => format!("({})", names.join(",")),
+                    }
+                })
+                .collect();
+            params.push(format!("tags={}", encode_query(&tags.join(","), "")));
+        }
+        if !self.filters.is_empty() {
+            params.push(format!(
+                "numericFilters={}",
+                encode_query(&self.filters.join(","), "")
+            ));
+        }
+        if self.page > 0 {
+            params.push(format!("page={}", self.page));
+        }
+        if let Some(hits) = self.hits_per_page {
+            params.push(format!("hitsPerPage={}", hits));
+        }
+        let path = if self.by_date {
+            "search_by_date"
+        } else {
+            "search"
+        };
+        format!("{}?{}", endpoint.build(path), params.join("&"))
+    }
+}
+
+/// A page of results of a [Search].
+#[derive(Debug)]
+pub struct SearchResults {
+    /// The matching items on this page.
+    pub hits: Vec<Hit>,
+    /// The total amount of matching items.
+    pub total: u64,
+    /// The number of this page, starting at 0.
+    pub page: usize,
+    /// The amount of pages available.
+    pub pages: usize,
+    /// How many results a page holds.
+    pub hits_per_page: usize,
+}
+
+impl SearchResults {
+    /// Returns whether there is a page after this one.
+    pub fn has_next(&self) -> bool {
+        self.page + 1 < self.pages
+    }
+}
+
+/// A matching item of a [Search].
+///
+/// Algolia does not return the ids of replies, so `kids` are always empty and polls
+/// have no options; fetch the item through the [Client] when those are needed.
+#[derive(Debug)]
+pub struct Hit {
+    /// The item.
+    pub item: AnyItem,
+    /// The id of the story the item belongs to, if known.
+    pub story_id: Option<u64>,
+    /// The title of the story the item belongs to, for comments.
+    pub story_title: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RawResults {
+    hits: Vec<RawHit>,
+    #[serde(rename = "nbHits")]
+    total: u64,
+    page: usize,
+    #[serde(rename = "nbPages")]
+    pages: usize,
+    #[serde(rename = "hitsPerPage")]
+    hits_per_page: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawHit {
+    #[serde(rename = "objectID")]
+    id: String,
+    #[serde(rename = "_tags")]
+    tags: Option<Vec<String>>,
+    author: Option<String>,
+    created_at_i: Option<u64>,
+    title: Option<String>,
+    url: Option<String>,
+    story_text: Option<String>,
+    comment_text: Option<String>,
+    points: Option<i64>,
+    num_comments: Option<u64>,
+    story_id: Option<u64>,
+    story_title: Option<String>,
+    parent_id: Option<u64>,
+}
+
+impl TryFrom<RawResults> for SearchResults {
+    type Error = HError;
+
+    fn try_from(raw: RawResults) -> Result<Self, Self::Error> {
+        Ok(SearchResults {
+            hits: raw
+                .hits
+                .into_iter()
+                .map(Hit::try_from)
+                .collect::<Result<_, _>>()?,
+            total: raw.total,
+            page: raw.page,
+            pages: raw.pages,
+            hits_per_page: raw.hits_per_page,
+        })
+    }
+}
+
+impl TryFrom<RawHit> for Hit {
+    type Error = HError;
+
+    fn try_from(hit: RawHit) -> Result<Self, Self::Error> {
+        let id = hit.id.parse().map_err(|_| HError::ConversionFailed)?;
+        let tags = convert_default!(hit.tags);
+        let kind = convert!(KINDS
+            .iter()
+            .find(|kind| tags.iter().any(|tag| tag == *kind)));
+        let needs_text = matches!(*kind, "story" | "poll" | "pollopt");
+        let item = Item {
+            id,
+            kind: Some(kind.to_string()),
+            by: hit.author,
+            time: hit.created_at_i,
+            text: hit
+                .story_text
+                .or(hit.comment_text)
+                .or_else(|| needs_text.then(String::new)),
+            parent: hit.parent_id,
+            poll: hit.story_id.filter(|_| *kind == "pollopt"),
+            url: hit
+                .url
+                .filter(|url| !url.is_empty())
+                .or_else(|| (*kind == "job").then(String::new)),
+            score: Some(convert_default!(hit.points)),
+            title: hit.title,
+            parts: (*kind == "poll").then(Vec::new),
+            descendants: Some(convert_default!(hit.num_comments)),
+            ..Default::default()
+        };
+        Ok(Hit {
+            item: item.try_into()?,
+            story_id: hit.story_id,
+            story_title: hit.story_title,
+        })
+    }
+}
+
+/// An item with its whole tree of children, as returned by the Algolia items endpoint.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RawNode {
+    pub(crate) id: u64,
+    #[serde(rename = "type")]
+    pub(crate) kind: Option<String>,
+    pub(crate) author: Option<String>,
+    pub(crate) created_at_i: Option<u64>,
+    pub(crate) title: Option<String>,
+    pub(crate) url: Option<String>,
+    pub(crate) text: Option<String>,
+    pub(crate) points: Option<i64>,
+    pub(crate) parent_id: Option<u64>,
+    pub(crate) children: Option<Vec<RawNode>>,
+    pub(crate) options: Option<Vec<RawNode>>,
+}
+
+impl RawNode {
+    /// Moves the node and its children into raw items, returning the amount of comments below it.
+    ///
+    /// Algolia leaves out the author and text of deleted items.
+    fn flatten(self, items: &mut HashMap<u64, Item>) -> u64 {
+        let children = convert_default!(self.children);
+        let kids: Vec<u64> = children.iter().map(|child| child.id).collect();
+        let parts = self
+            .options
+            .map(|options| options.iter().map(|option| option.id).collect());
+        let mut descendants = 0;
+        for child in children {
+            descendants += 1 + child.flatten(items);
+        }
+
+        let kind = convert_default!(self.kind);
+        let needs_text = matches!(kind.as_str(), "story" | "poll" | "pollopt");
+        let item = Item {
+            id: self.id,
+            deleted: self.author.is_none().then_some(true),
+            by: self.author,
+            time: self.created_at_i,
+            text: self.text.or_else(|| needs_text.then(String::new)),
+            parent: self.parent_id.filter(|_| kind == "comment"),
+            poll: self.parent_id.filter(|_| kind == "pollopt"),
+            kids: Some(kids).filter(|kids| !kids.is_empty()),
+            url: self
+                .url
+                .filter(|url| !url.is_empty())
+                .or_else(|| (kind == "job").then(String::new)),
+            score: Some(convert_default!(self.points)),
+            title: self.title,
+            parts: parts.or_else(|| (kind == "poll").then(Vec::new)),
+            descendants: Some(descendants),
+            kind: Some(kind),
+            ..Default::default()
+        };
+        items.insert(self.id, item);
+        descendants
+    }
+}
+
+/// Builds the same [Thread] as [Client::get_thread] from an Algolia item tree.
+pub(crate) fn thread(root: RawNode) -> Result<Thread, HError> {
+    let id = root.id;
+    let mut items = HashMap::new();
+    root.flatten(&mut items);
+    let root = convert!(items.remove(&id));
+    thread::build(root, |ids| {
+        ids.iter()
+            .map(|id| items.get(id).cloned().ok_or(HError::NotFound))
+            .collect()
+    })
+}
@@ -0,0 +1,169 @@
+//! Destinations for fetched data, with a JSON Lines implementation.
+
+use crate::prelude::*;
+use miniserde::json::{from_str, to_string};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Lines, Write};
+use std::path::Path;
+
+/// A destination for raw items and users, such as a file or a database.
+pub trait Sink {
+    /// Writes a single item.
+    fn write_item(&mut self, item: &Item) -> Result<(), HError>;
+
+    /// Writes a single user.
+    fn write_user(&mut self, user: &User) -> Result<(), HError>;
+
+    /// Flushes buffered writes, called when a crawl checkpoints or finishes.
+    fn flush(&mut self) -> Result<(), HError> {
+        Ok(())
+    }
+}
+
+impl<S: Sink + ?Sized> Sink for &mut S {
+    fn write_item(&mut self, item: &Item) -> Result<(), HError> {
+        (**self).write_item(item)
+    }
+
+    fn write_user(&mut self, user: &User) -> Result<(), HError> {
+        (**self).write_user(user)
+    }
+
+    fn flush(&mut self) -> Result<(), HError> {
+        (**self).flush()
+    }
+}
+
+/// Collects items in memory, ignoring users.
+impl Sink for Vec<Item> {
+    fn write_item(&mut self, item: &Item) -> Result<(), HError> {
+        self.push(item.clone());
+        Ok(())
+    }
+
+    fn write_user(&mut self, _user: &User) -> Result<(), HError> {
+        Ok(())
+    }
+}
+
+/// Writes the raw JSON of every item and user as one line each.
+#[derive(Debug)]
+pub struct JsonLines<W: Write> {
+    writer: BufWriter<W>,
+}
+
+impl JsonLines<File> {
+    /// Creates or truncates the file at the path and writes to it.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, HError> {
+        Ok(Self::new(File::create(path)?))
+    }
+
+    /// Opens the file at the path, creating it if needed, and appends to it.
+    pub fn append(path: impl AsRef<Path>) -> Result<Self, HError> {
+        let file = File::options().create(true).append(true).open(path)?;
+        Ok(Self::new(file))
+    }
+}
+
+impl<W: Write> JsonLines<W> {
+    /// Writes to a writer, buffering the output.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: BufWriter::new(writer),
+        }
+    }
+
+    /// Flushes and returns the underlying writer.
+    pub fn into_inner(self) -> Result<W, HError> {
+        self.writer
+            .into_inner()
+            .map_err(|e| HError::Io(e.into_error()))
+    }
+
+    fn write_line(&mut self, json: &str) -> Result<(), HError> {
+        self.writer.write_all(json.as_bytes())?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+impl<W: Write> Sink for JsonLines<W> {
+    fn write_item(&mut self, item: &Item) -> Result<(), HError> {
+        self.write_line(&to_string(item))
+    }
+
+    fn write_user(&mut self, user: &User) -> Result<(), HError> {
+        self.write_line(&to_string(user))
+    }
+
+    fn flush(&mut self) -> Result<(), HError> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// A single line of a JSON Lines dump.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum Record {
+    /// A raw item.
+    Item(Item),
+    /// A user.
+    User(User),
+}
+
+/// Reads back a dump written by [JsonLines], skipping blank lines.
+#[derive(Debug)]
+pub struct JsonLinesReader<R: BufRead> {
+    lines: Lines<R>,
+}
+
+impl JsonLinesReader<BufReader<File>> {
+    /// Opens the file at the path for reading.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, HError> {
+        Ok(Self::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R: BufRead> JsonLinesReader<R> {
+    /// Reads from a buffered reader.
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+        }
+    }
+
+    /// Returns only the items of the dump, converted into [AnyItem].
+    pub fn items(self) -> impl Iterator<Item = Result<AnyItem, HError>> {
+        self.filter_map(|record| match record {
+            Ok(Record::Item(item)) => Some(item.try_into()),
+            Ok(Record::User(_)) => None,
+            Err(e) => Some(Err(e)),
+        })
+    }
+}
+
+impl<R: BufRead> Iterator for JsonLinesReader<R> {
+    type Item = Result<Record, HError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e.into())),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            // Users have a string id, so they never parse as an item.
+            if let Ok(item) = from_str::<Item>(&line) {
+                return Some(Ok(Record::Item(item)));
+            }
+            return Some(
+                from_str::<User>(&line)
+                    .map(Record::User)
+                    .map_err(HError::from),
+            );
+        }
+    }
+}
@@ -0,0 +1,265 @@
+//! Domain and site label parsing for submission URLs.
+
+/// Path-based sites where Hacker News includes the first path segment in the site label.
+const USER_PATH_SITES: &[&str] = &[
+    "github.com",
+    "gitlab.com",
+    "bitbucket.org",
+    "twitter.com",
+    "x.com",
+    "medium.com",
+];
+
+/// Query parameters that only track where a visitor came from.
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "dclid", "msclkid", "mc_cid", "mc_eid", "ref", "ref_src", "ref_url",
+    "source", "igshid", "_hsenc", "_hsmi",
+];
+
+pub(crate) struct Url<'a> {
+    pub(crate) scheme: String,
+    pub(crate) host: String,
+    pub(crate) port: Option<&'a str>,
+    pub(crate) path: &'a str,
+    pub(crate) query: Option<&'a str>,
+}
+
+impl<'a> Url<'a> {
+    pub(crate) fn parse(url: &'a str) -> Option<Self> {
+        let url = url.trim();
+        let (scheme, rest) = url.split_once("://")?;
+        let valid_scheme = scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+        if scheme.is_empty() || !valid_scheme {
+            return None;
+        }
+
+        let rest = rest.split('#').next().unwrap_or_default();
+        let authority_end = rest.find(['/', '?']).unwrap_or(rest.len());
+        let (authority, rest) = rest.split_at(authority_end);
+        let authority = authority.rsplit('@').next().unwrap_or_default();
+        let (host, port) = if let Some(ipv6) = authority.strip_prefix('[') {
+            let (host, after) = ipv6.split_once(']')?;
+            (host, after.strip_prefix(':'))
+        } else {
+            match authority.rsplit_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (authority, None),
+            }
+        };
+        let host = host.trim_end_matches('.').to_lowercase();
+        if host.is_empty() {
+            return None;
+        }
+
+        let (path, query) = match rest.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (rest, None),
+        };
+        Some(Url {
+            scheme: scheme.to_ascii_lowercase(),
+            host,
+            port: port.filter(|port| !port.is_empty()),
+            path,
+            query,
+        })
+    }
+}
+
+/// Percent-encodes a query parameter value, leaving unreserved characters and `keep` as they are.
+pub(crate) fn encode_query(value: &str, keep: &str) -> String {
+    let mut out = String::new();
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric()
+            || b"-_.~".contains(&byte)
+            || keep.as_bytes().contains(&byte)
+        {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
+
+/// Decodes a percent-encoded query parameter value, where `+` stands for a space.
+pub(crate) fn decode_query(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+            (b'+', _) => out.push(b' '),
+            (byte, _) => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Returns the domain of a URL as Hacker News displays it.
+///
+/// The host is lowercased, a leading `www.` is removed and internationalized labels are
+/// decoded from punycode. Returns [None] if the URL has no host.
+pub fn domain(url: &str) -> Option<String> {
+    let url = Url::parse(url)?;
+    let host = url.host.strip_prefix("www.").unwrap_or(&url.host);
+    let labels: Vec<String> = host
+        .split('.')
+        .map(|label| {
+            label
+                .strip_prefix("xn--")
+                .and_then(punycode_decode)
+                .unwrap_or_else(|| label.to_string())
+        })
+        .collect();
+    Some(labels.join("."))
+}
+
+/// Returns the site label Hacker News shows next to a title, such as `github.com/rust-lang`.
+///
+/// For most sites this is the [domain], but for sites hosting many unrelated users under
+/// one domain the user part of the path is included as well.
+pub fn site_label(url: &str) -> Option<String> {
+    let domain = domain(url)?;
+    if !USER_PATH_SITES.contains(&domain.as_str()) {
+        return Some(domain);
+    }
+    let path = Url::parse(url)?.path;
+    let user = path.split('/').find(|segment| !segment.is_empty());
+    match user {
+        Some(user) if domain != "medium.com" || user.starts_with('@') => {
+            Some(format!("{}/{}", domain, user))
+        }
+        _ => Some(domain),
+    }
+}
+
+/// Normalizes a URL so the same article submitted under different URLs compares equal.
+///
+/// The scheme is unified to `https`, the host is lowercased and stripped of `www.`, default
+/// ports, fragments, trailing slashes and tracking parameters such as `utm_source` are removed,
+/// and the remaining query parameters are sorted. Returns [None] if the URL has no host.
+pub fn normalize(url: &str) -> Option<String> {
+    let url = Url::parse(url)?;
+    let scheme = match url.scheme.as_str() {
+        "http" => "https",
+        scheme => scheme,
+    };
+    let host = url.host.strip_prefix("www.").unwrap_or(&url.host);
+    let host = if host.contains(':') {
+        format!("[{}]", host)
+    } else {
+        host.to_string()
+    };
+    let port = match url.port {
+        Some("80") | Some("443") | None => String::new(),
+        Some(port) => format!(":{}", port),
+    };
+
+    let mut path = url.path;
+    for index in ["index.html", "index.htm", "index.php"] {
+        if let Some(stripped) = path.strip_suffix(index) {
+            if stripped.ends_with('/') {
+                path = stripped;
+            }
+        }
+    }
+    let path = path.trim_end_matches('/');
+
+    let mut params: Vec<&str> = url
+        .query
+        .unwrap_or_default()
+        .split('&')
+        .filter(|param| !param.is_empty())
+        .filter(|param| {
+            let name = param.split('=').next().unwrap_or_default();
+            !name.starts_with("utm_") && !TRACKING_PARAMS.contains(&name)
+        })
+        .collect();
+    params.sort_unstable();
+    let query = if params.is_empty() {
+        String::new()
+    } else {
+        format!("?{}", params.join("&"))
+    };
+
+    Some(format!("{}://{}{}{}{}", scheme, host, port, path, query))
+}
+
+fn punycode_decode(input: &str) -> Option<String> {
+    const BASE: u32 = 36;
+    const T_MIN: u32 = 1;
+    const T_MAX: u32 = 26;
+
+    let (basic, extended) = match input.rfind('-') {
+        Some(split) => (&input[..split], &input[split + 1..]),
+        None => ("", input),
+    };
+    if !basic.is_ascii() {
+        return None;
+    }
+    let mut output: Vec<char> = basic.chars().collect();
+    let mut code_point: u32 = 128;
+    let mut bias: u32 = 72;
+    let mut index: u32 = 0;
+    let mut digits = extended.bytes().peekable();
+    let mut first = true;
+
+    while digits.peek().is_some() {
+        let old_index = index;
+        let mut weight: u32 = 1;
+        let mut k = BASE;
+        loop {
+            let digit = match digits.next()? {
+                byte @ b'a'..=b'z' => byte - b'a',
+                byte @ b'A'..=b'Z' => byte - b'A',
+                byte @ b'0'..=b'9' => byte - b'0' + 26,
+                _ => return None,
+            } as u32;
+            index = index.checked_add(digit.checked_mul(weight)?)?;
+            let threshold = if k <= bias {
+                T_MIN
+            } else if k >= bias + T_MAX {
+                T_MAX
+            } else {
+                k - bias
+            };
+            if digit < threshold {
+                break;
+            }
+            weight = weight.checked_mul(BASE - threshold)?;
+            k += BASE;
+        }
+
+        let length = output.len() as u32 + 1;
+        bias = {
+            let mut delta = index - old_index;
+            delta /= if first { 700 } else { 2 };
+            delta += delta / length;
+            let mut k = 0;
+            while delta > ((BASE - T_MIN) * T_MAX) / 2 {
+                delta /= BASE - T_MIN;
+                k += BASE;
+            }
+            k + ((BASE - T_MIN + 1) * delta) / (delta + 38)
+        };
+        first = false;
+        code_point = code_point.checked_add(index / length)?;
+        index %= length;
+        output.insert(index as usize, char::from_u32(code_point)?);
+        index += 1;
+    }
+
+    Some(output.into_iter().collect())
+}
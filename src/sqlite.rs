@@ -0,0 +1,311 @@
+//! SQLite export and query layer, enabled with the `sqlite` feature.
+//!
+//! The schema has one table each for items, users, the edges between items and
+//! their kids, and snapshots of the story lists. Every row of `items` and `users`
+//! also keeps the raw JSON, so nothing is lost and [SqliteStore] can hand back the
+//! same types as [Client].
+
+use crate::prelude::*;
+use crate::sink::Sink;
+use crate::thread::{self, Thread};
+use miniserde::json::{from_str, to_string};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Writes are committed in transactions of at most this many rows.
+const BATCH_SIZE: usize = 1000;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS items (
+    id INTEGER PRIMARY KEY,
+    type TEXT,
+    by TEXT,
+    time INTEGER,
+    title TEXT,
+    url TEXT,
+    text TEXT,
+    score INTEGER,
+    parent INTEGER,
+    poll INTEGER,
+    descendants INTEGER,
+    deleted INTEGER NOT NULL,
+    dead INTEGER NOT NULL,
+    raw TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS items_by ON items (by);
+CREATE INDEX IF NOT EXISTS items_parent ON items (parent);
+CREATE TABLE IF NOT EXISTS users (
+    id TEXT PRIMARY KEY,
+    created INTEGER NOT NULL,
+    karma INTEGER NOT NULL,
+    about TEXT NOT NULL,
+    raw TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS kids (
+    parent INTEGER NOT NULL,
+    kid INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (parent, kid)
+);
+CREATE INDEX IF NOT EXISTS kids_kid ON kids (kid);
+CREATE TABLE IF NOT EXISTS list_snapshots (
+    list TEXT NOT NULL,
+    taken_at INTEGER NOT NULL,
+    position INTEGER NOT NULL,
+    item INTEGER NOT NULL,
+    PRIMARY KEY (list, taken_at, position)
+);
+";
+
+fn open(path: impl AsRef<Path>) -> Result<Connection, HError> {
+    let connection = Connection::open(path)?;
+    connection.execute_batch(SCHEMA)?;
+    Ok(connection)
+}
+
+/// A [Sink] upserting items and users into a SQLite database.
+///
+/// Writes are batched in transactions, which are committed on [flush](Sink::flush),
+/// every thousand rows, and when the sink is dropped.
+#[derive(Debug)]
+pub struct SqliteSink {
+    connection: Connection,
+    pending: usize,
+}
+
+impl SqliteSink {
+    /// Opens the database at the path, creating it and the schema if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, HError> {
+        Ok(Self {
+            connection: open(path)?,
+            pending: 0,
+        })
+    }
+
+    /// Records the current order of a list, such as `topstories`, as a new snapshot.
+    ///
+    /// Snapshots have a resolution of one second, a later one in the same second replaces
+    /// the earlier one.
+    pub fn write_list(&mut self, list: &str, ids: &[u64]) -> Result<(), HError> {
+        self.begin()?;
+        let taken_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs())
+            .unwrap_or_default();
+        self.connection
+            .prepare_cached("DELETE FROM list_snapshots WHERE list = ?1 AND taken_at = ?2")?
+            .execute(params![list, taken_at])?;
+        let mut insert = self.connection.prepare_cached(
+            "INSERT INTO list_snapshots (list, taken_at, position, item)
+             VALUES (?1, ?2, ?3, ?4)",
+        )?;
+        for (position, id) in ids.iter().enumerate() {
+            insert.execute(params![list, taken_at, position, id])?;
+        }
+        drop(insert);
+        self.written(ids.len())
+    }
+
+    fn begin(&mut self) -> Result<(), HError> {
+        if self.pending == 0 && self.connection.is_autocommit() {
+            self.connection.execute_batch("BEGIN")?;
+        }
+        Ok(())
+    }
+
+    fn written(&mut self, rows: usize) -> Result<(), HError> {
+        self.pending += rows.max(1);
+        if self.pending >= BATCH_SIZE {
+            self.flush()?;
+        }
+        Ok(())
+    }
+}
+
+impl Sink for SqliteSink {
+    fn write_item(&mut self, item: &Item) -> Result<(), HError> {
+        self.begin()?;
+        self.connection
+            .prepare_cached(
+                "INSERT INTO items (id, type, by, time, title, url, text, score, parent, poll,
+                                    descendants, deleted, dead, raw)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+                 ON CONFLICT (id) DO UPDATE SET
+                    type = excluded.type, by = excluded.by, time = excluded.time,
+                    title = excluded.title, url = excluded.url, text = excluded.text,
+                    score = excluded.score, parent = excluded.parent, poll = excluded.poll,
+                    descendants = excluded.descendants, deleted = excluded.deleted,
+                    dead = excluded.dead, raw = excluded.raw",
+            )?
+            .execute(params![
+                item.id,
+                item.kind,
+                item.by,
+                item.time,
+                item.title,
+                item.url,
+                item.text,
+                item.score,
+                item.parent,
+                item.poll,
+                item.descendants,
+                item.is_deleted(),
+                item.is_dead(),
+                to_string(item),
+            ])?;
+
+        self.connection
+            .prepare_cached("DELETE FROM kids WHERE parent = ?1")?
+            .execute(params![item.id])?;
+        let mut insert = self
+            .connection
+            .prepare_cached("INSERT INTO kids (parent, kid, position) VALUES (?1, ?2, ?3)")?;
+        for (position, kid) in item.kids.iter().flatten().enumerate() {
+            insert.execute(params![item.id, kid, position])?;
+        }
+        drop(insert);
+        self.written(1)
+    }
+
+    fn write_user(&mut self, user: &User) -> Result<(), HError> {
+        self.begin()?;
+        self.connection
+            .prepare_cached(
+                "INSERT INTO users (id, created, karma, about, raw) VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT (id) DO UPDATE SET
+                    created = excluded.created, karma = excluded.karma,
+                    about = excluded.about, raw = excluded.raw",
+            )?
+            .execute(params![
+                user.id,
+                user.created,
+                user.karma,
+                user.about,
+                to_string(user),
+            ])?;
+        self.written(1)
+    }
+
+    fn flush(&mut self) -> Result<(), HError> {
+        if !self.connection.is_autocommit() {
+            self.connection.execute_batch("COMMIT")?;
+        }
+        self.pending = 0;
+        Ok(())
+    }
+}
+
+impl Drop for SqliteSink {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// Reads back a database written by [SqliteSink], mirroring the methods of [Client].
+#[derive(Debug)]
+pub struct SqliteStore {
+    connection: Connection,
+}
+
+impl SqliteStore {
+    /// Opens the database at the path, creating it and the schema if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, HError> {
+        Ok(Self {
+            connection: open(path)?,
+        })
+    }
+
+    /// Returns the raw item with the id.
+    pub fn get_item(&self, id: u64) -> Result<Item, HError> {
+        let raw: Option<String> = self
+            .connection
+            .prepare_cached("SELECT raw FROM items WHERE id = ?1")?
+            .query_row(params![id], |row| row.get(0))
+            .optional()?;
+        let raw = raw.ok_or(HError::NotFound)?;
+        Ok(from_str(&raw)?)
+    }
+
+    /// Returns the item with the id, whatever its kind.
+    pub fn get_any(&self, id: u64) -> Result<AnyItem, HError> {
+        self.get_item(id)?.try_into()
+    }
+
+    /// Returns the [Ask] with the id.
+    pub fn get_ask(&self, id: u64) -> Result<Ask, HError> {
+        self.get_item(id)?.try_into()
+    }
+
+    /// Returns the [Comment] with the id.
+    pub fn get_comment(&self, id: u64) -> Result<Comment, HError> {
+        self.get_item(id)?.try_into()
+    }
+
+    /// Returns the [Job] with the id.
+    pub fn get_job(&self, id: u64) -> Result<Job, HError> {
+        self.get_item(id)?.try_into()
+    }
+
+    /// Returns the [Poll] with the id.
+    pub fn get_poll(&self, id: u64) -> Result<Poll, HError> {
+        self.get_item(id)?.try_into()
+    }
+
+    /// Returns the [PollOption] with the id.
+    pub fn get_poll_option(&self, id: u64) -> Result<PollOption, HError> {
+        self.get_item(id)?.try_into()
+    }
+
+    /// Returns the [Story] with the id.
+    pub fn get_story(&self, id: u64) -> Result<Story, HError> {
+        self.get_item(id)?.try_into()
+    }
+
+    /// Returns the [User] with the username.
+    pub fn get_user(&self, name: &str) -> Result<User, HError> {
+        let raw: Option<String> = self
+            .connection
+            .prepare_cached("SELECT raw FROM users WHERE id = ?1")?
+            .query_row(params![name], |row| row.get(0))
+            .optional()?;
+        let raw = raw.ok_or(HError::NotFound)?;
+        Ok(from_str(&raw)?)
+    }
+
+    /// Returns the most recent snapshot of a list, or an empty list if none was taken.
+    pub fn get_list(&self, list: &str) -> Result<Vec<u64>, HError> {
+        let mut select = self.connection.prepare_cached(
+            "SELECT item FROM list_snapshots
+             WHERE list = ?1
+               AND taken_at = (SELECT MAX(taken_at) FROM list_snapshots WHERE list = ?1)
+             ORDER BY position",
+        )?;
+        let ids = select
+            .query_map(params![list], |row| row.get(0))?
+            .collect::<Result<_, _>>()?;
+        Ok(ids)
+    }
+
+    /// Returns an item with its tree of comments, like [Client::get_thread].
+    ///
+    /// Comments missing from the database are kept as deleted ones.
+    pub fn thread(&self, id: u64) -> Result<Thread, HError> {
+        thread::build(self.get_item(id)?, |ids| {
+            ids.iter().map(|id| self.get_item(*id)).collect()
+        })
+    }
+
+    /// Returns every stored item posted by the user, newest first.
+    pub fn user_submissions(&self, name: &str) -> Result<Vec<AnyItem>, HError> {
+        let mut select = self
+            .connection
+            .prepare_cached("SELECT raw FROM items WHERE by = ?1 ORDER BY id DESC")?;
+        let rows = select.query_map(params![name], |row| row.get::<_, String>(0))?;
+        rows.map(|raw| {
+            let item: Item = from_str(&raw?)?;
+            item.try_into()
+        })
+        .collect()
+    }
+}
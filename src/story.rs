@@ -1,6 +1,7 @@
 //! Story struct and associated methods.
 
 use crate::prelude::*;
+use crate::site;
 
 #[non_exhaustive]
 #[derive(Debug)]
@@ -36,4 +37,34 @@ impl Story {
             .map(|kid| client.get_comment(*kid))
             .collect()
     }
+
+    /// Returns the domain this story links to, such as `example.com`.
+    pub fn domain(&self) -> Option<String> {
+        site::domain(&self.url)
+    }
+
+    /// Returns the site label Hacker News shows next to the title, such as `github.com/user`.
+    pub fn site_label(&self) -> Option<String> {
+        site::site_label(&self.url)
+    }
+
+    /// Returns the page of this story on the Hacker News website.
+    pub fn permalink(&self) -> String {
+        self.permalink_on(&WebHost::default())
+    }
+
+    /// Returns the page of this story on a different web host.
+    pub fn permalink_on(&self, host: &WebHost) -> String {
+        host.item(self.id)
+    }
+
+    /// Returns the page listing other submissions from the same site, if the story has a URL.
+    pub fn site_permalink(&self) -> Option<String> {
+        self.site_permalink_on(&WebHost::default())
+    }
+
+    /// Returns the page listing other submissions from the same site on a different web host.
+    pub fn site_permalink_on(&self, host: &WebHost) -> Option<String> {
+        self.site_label().map(|site| host.from_site(&site))
+    }
 }
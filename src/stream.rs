@@ -0,0 +1,401 @@
+//! Streaming subscriptions using the Server-Sent Events interface of Firebase.
+
+use crate::prelude::*;
+use miniserde::json::{self, Number, Object, Value};
+use std::io::{BufRead, BufReader, Read};
+use std::thread;
+use std::time::Duration;
+use ureq::Agent;
+
+/// Consecutive failed connection attempts before a [Subscription] gives up.
+const MAX_RETRIES: u32 = 5;
+
+/// Consecutive dropped connections without any event before a [Subscription] gives up.
+const MAX_DROPS: u32 = 5;
+
+/// A location in the API that can be subscribed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Path {
+    /// The latest item id.
+    MaxItem,
+    /// The top stories.
+    TopStories,
+    /// The recently changed items and profiles.
+    Updates,
+    /// A single item.
+    Item(u64),
+}
+
+impl Path {
+    pub(crate) fn url(&self, endpoint: &Endpoint) -> String {
+        match self {
+            Path::MaxItem => endpoint.maxitem(),
+            Path::TopStories => endpoint.topstories(),
+            Path::Updates => endpoint.updates(),
+            Path::Item(id) => endpoint.item(*id),
+        }
+    }
+}
+
+/// A raw event sent by Firebase.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub enum StreamEvent {
+    /// The data at a path was replaced.
+    Put {
+        /// The path relative to the subscribed location, `/` for the whole of it.
+        path: String,
+        /// The new data, [Value::Null] if it was removed.
+        data: Value,
+    },
+    /// Some children of the data at a path were replaced.
+    Patch {
+        /// The path relative to the subscribed location.
+        path: String,
+        /// An object of the children that were replaced.
+        data: Value,
+    },
+    /// Sent periodically to keep the connection open.
+    KeepAlive,
+    /// The server closed the subscription.
+    Cancel,
+    /// The credentials of the subscription expired.
+    AuthRevoked,
+}
+
+/// The current state of a subscribed [Path], sent after every change.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum Update {
+    /// The latest item id.
+    MaxItem(u64),
+    /// The ids of the top stories, in order.
+    TopStories(Vec<u64>),
+    /// The recently changed items and profiles.
+    Updates {
+        /// Ids of items that changed.
+        items: Vec<u64>,
+        /// Usernames of profiles that changed.
+        profiles: Vec<String>,
+    },
+    /// The item, or [None] if it does not exist (yet).
+    Item(Option<AnyItem>),
+}
+
+/// A live subscription to a [Path], created by [Client::subscribe].
+///
+/// Iterating yields the typed state after every change. Dropped connections are
+/// reestablished with a growing delay, and the iterator ends once the server cancels the
+/// subscription or keeps closing connections without sending any event.
+pub struct Subscription {
+    agent: Agent,
+    url: String,
+    path: Path,
+    reader: Option<Box<dyn BufRead + Send>>,
+    state: Value,
+    drops: u32,
+    done: bool,
+}
+
+impl std::fmt::Debug for Subscription {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("Subscription")
+            .field("url", &self.url)
+            .field("path", &self.path)
+            .field("state", &self.state)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+impl Subscription {
+    pub(crate) fn connect(agent: Agent, url: String, path: Path) -> Result<Self, HError> {
+        let mut subscription = Self {
+            agent,
+            url,
+            path,
+            reader: None,
+            state: Value::Null,
+            drops: 0,
+            done: false,
+        };
+        subscription.reconnect()?;
+        Ok(subscription)
+    }
+
+    /// Returns the subscribed path.
+    pub fn path(&self) -> Path {
+        self.path
+    }
+
+    /// Returns the current raw state of the subscribed path.
+    pub fn state(&self) -> &Value {
+        &self.state
+    }
+
+    fn reconnect(&mut self) -> Result<(), HError> {
+        let mut attempt = 0;
+        loop {
+            let response = self
+                .agent
+                .get(&self.url)
+                .set("Accept", "text/event-stream")
+                .call();
+            match response {
+                Ok(response) => {
+                    let reader: Box<dyn Read + Send> = response.into_reader();
+                    self.reader = Some(Box::new(BufReader::new(reader)));
+                    return Ok(());
+                }
+                Err(e) if attempt + 1 >= MAX_RETRIES => return Err(e.into()),
+                Err(_) => {
+                    thread::sleep(Duration::from_millis(500 << attempt));
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Waits for the next raw event, applying it to the [state](Subscription::state).
+    ///
+    /// Returns [None] once the subscription was cancelled by the server, and the last error
+    /// once the connection dropped too often in a row without delivering an event.
+    pub fn next_event(&mut self) -> Result<Option<StreamEvent>, HError> {
+        if self.done {
+            return Ok(None);
+        }
+        loop {
+            let event = match self.reader.as_mut() {
+                Some(reader) => read_event(reader),
+                None => Ok(None),
+            };
+            match event {
+                Ok(Some((name, data))) => {
+                    let Some(event) = parse_event(&name, &data)? else {
+                        continue;
+                    };
+                    self.drops = 0;
+                    match &event {
+                        StreamEvent::Put { path, data } => put(&mut self.state, path, data.clone()),
+                        StreamEvent::Patch { path, data } => patch(&mut self.state, path, data),
+                        StreamEvent::Cancel | StreamEvent::AuthRevoked => {
+                            self.done = true;
+                            self.reader = None;
+                        }
+                        StreamEvent::KeepAlive => {}
+                    }
+                    return Ok(Some(event));
+                }
+                Ok(None) | Err(_) if self.drops + 1 >= MAX_DROPS => {
+                    self.reader = None;
+                    return Err(match event {
+                        Err(e) => e,
+                        _ => std::io::Error::new(
+                            std::io::ErrorKind::UnexpectedEof,
+                            "stream closed without events",
+                        )
+                        .into(),
+                    });
+                }
+                Ok(None) | Err(_) => {
+                    self.reader = None;
+                    thread::sleep(Duration::from_millis(500 << self.drops));
+                    self.drops += 1;
+                    self.reconnect()?;
+                }
+            }
+        }
+    }
+
+    fn update(&self) -> Result<Update, HError> {
+        let state = normalize(&self.state);
+        Ok(match self.path {
+            Path::MaxItem => Update::MaxItem(convert!(as_u64(&state))),
+            Path::TopStories => Update::TopStories(json::from_str(&json::to_string(&state))?),
+            Path::Updates => {
+                let update: crate::item::Update = json::from_str(&json::to_string(&state))?;
+                Update::Updates {
+                    items: update.items,
+                    profiles: update.profiles,
+                }
+            }
+            Path::Item(_) => match state {
+                Value::Null => Update::Item(None),
+                state => {
+                    let item: Item = json::from_str(&json::to_string(&state))?;
+                    Update::Item(Some(item.try_into()?))
+                }
+            },
+        })
+    }
+}
+
+impl Iterator for Subscription {
+    type Item = Result<Update, HError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_event() {
+                Ok(Some(StreamEvent::Put { .. })) | Ok(Some(StreamEvent::Patch { .. })) => {
+                    return Some(self.update());
+                }
+                Ok(Some(_)) => continue,
+                Ok(None) => return None,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+/// Reads lines until a complete event, returning its name and data, or [None] at the end.
+fn read_event(reader: &mut impl BufRead) -> Result<Option<(String, String)>, HError> {
+    let mut name = String::new();
+    let mut data: Vec<String> = vec![];
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            if name.is_empty() && data.is_empty() {
+                continue;
+            }
+            return Ok(Some((name, data.join("\n"))));
+        }
+        if line.starts_with(':') {
+            continue;
+        }
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => name = value.to_string(),
+            "data" => data.push(value.to_string()),
+            _ => {}
+        }
+    }
+}
+
+/// Parses an event, returning [None] for unknown event names.
+fn parse_event(name: &str, data: &str) -> Result<Option<StreamEvent>, HError> {
+    let payload = || -> Result<(String, Value), HError> {
+        let value: Value = json::from_str(data)?;
+        let Value::Object(mut object) = value else {
+            return Err(HError::ConversionFailed);
+        };
+        let path = match object.remove("path") {
+            Some(Value::String(path)) => path,
+            _ => return Err(HError::ConversionFailed),
+        };
+        Ok((path, object.remove("data").unwrap_or(Value::Null)))
+    };
+    Ok(Some(match name {
+        "put" => {
+            let (path, data) = payload()?;
+            StreamEvent::Put { path, data }
+        }
+        "patch" => {
+            let (path, data) = payload()?;
+            StreamEvent::Patch { path, data }
+        }
+        "keep-alive" => StreamEvent::KeepAlive,
+        "cancel" => StreamEvent::Cancel,
+        "auth_revoked" => StreamEvent::AuthRevoked,
+        _ => return Ok(None),
+    }))
+}
+
+fn segments(path: &str) -> Vec<&str> {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+fn put(state: &mut Value, path: &str, data: Value) {
+    set(state, &segments(path), data);
+}
+
+fn patch(state: &mut Value, path: &str, data: &Value) {
+    let Value::Object(children) = data else {
+        return;
+    };
+    let base = segments(path);
+    for (key, value) in children.iter() {
+        let mut full = base.clone();
+        full.extend(segments(key));
+        set(state, &full, value.clone());
+    }
+}
+
+fn set(state: &mut Value, path: &[&str], data: Value) {
+    let Some((first, rest)) = path.split_first() else {
+        *state = data;
+        return;
+    };
+    if let Value::Array(array) = state {
+        let object: Object = array
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (index.to_string(), value.clone()))
+            .collect();
+        *state = Value::Object(object);
+    }
+    if !matches!(state, Value::Object(_)) {
+        *state = Value::Object(Object::new());
+    }
+    let Value::Object(object) = state else {
+        return;
+    };
+    if rest.is_empty() && matches!(data, Value::Null) {
+        object.remove(*first);
+        return;
+    }
+    let child = object.entry(first.to_string()).or_insert(Value::Null);
+    set(child, rest, data);
+}
+
+/// Turns objects with only numeric keys back into arrays, the way Firebase stores lists.
+fn normalize(value: &Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let numeric =
+                !object.is_empty() && object.keys().all(|key| key.parse::<usize>().is_ok());
+            if numeric {
+                let mut entries: Vec<(usize, Value)> = object
+                    .iter()
+                    .filter(|(_, value)| !matches!(value, Value::Null))
+                    .map(|(key, value)| (key.parse().unwrap_or_default(), normalize(value)))
+                    .collect();
+                entries.sort_by_key(|(index, _)| *index);
+                Value::Array(entries.into_iter().map(|(_, value)| value).collect())
+            } else {
+                Value::Object(
+                    object
+                        .iter()
+                        .map(|(key, value)| (key.clone(), normalize(value)))
+                        .collect(),
+                )
+            }
+        }
+        Value::Array(array) => Value::Array(
+            array
+                .iter()
+                .filter(|value| !matches!(value, Value::Null))
+                .map(normalize)
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
+
+fn as_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(Number::U64(n)) => Some(*n),
+        Value::Number(Number::I64(n)) => u64::try_from(*n).ok(),
+        _ => None,
+    }
+}
@@ -0,0 +1,394 @@
+//! In-process stand-in for the Hacker News API, enabled with the `testing` feature.
+//!
+//! A [MockServer] serves a [Dataset] over plain HTTP on a local port, so a [Client]
+//! pointed at its [base URL](MockServer::base_url) behaves like against Firebase.
+//! [Faults](Fault) can be injected to exercise retries and error handling.
+
+use crate::builder::ThreadBuilder;
+use crate::dump::Dump;
+use crate::prelude::*;
+use crate::search::RawNode;
+use miniserde::json::{from_str, to_string};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// The data served by a [MockServer].
+///
+/// Items, users and lists are answered like by a [Dump], with updates on top.
+#[derive(Debug, Default, Clone)]
+pub struct Dataset {
+    dump: Dump,
+    updates: (Vec<u64>, Vec<String>),
+}
+
+impl Dataset {
+    /// Creates an empty dataset.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an item, replacing any item with the same id.
+    pub fn item(mut self, item: &Item) -> Self {
+        self.dump.insert_item(item.id(), to_string(item));
+        self
+    }
+
+    /// Adds every item, such as those of a [ThreadBuilder].
+    pub fn items(mut self, items: &[Item]) -> Self {
+        for item in items {
+            self = self.item(item);
+        }
+        self
+    }
+
+    /// Adds the story and comments of a thread.
+    pub fn thread(self, thread: &ThreadBuilder) -> Self {
+        self.items(&thread.items())
+    }
+
+    /// Adds an item from its raw JSON, which must at least contain an id.
+    pub fn item_json(mut self, json: &str) -> Result<Self, HError> {
+        let item: Item = miniserde::json::from_str(json)?;
+        self.dump.insert_item(item.id(), json.to_string());
+        Ok(self)
+    }
+
+    /// Adds a user, replacing any user with the same name.
+    pub fn user(mut self, user: &User) -> Self {
+        self.dump.insert_user(user.id.clone(), to_string(user));
+        self
+    }
+
+    /// Adds a user from its raw JSON.
+    pub fn user_json(mut self, json: &str) -> Result<Self, HError> {
+        let user: User = miniserde::json::from_str(json)?;
+        self.dump.insert_user(user.id, json.to_string());
+        Ok(self)
+    }
+
+    /// Sets the ids of a story list, in ranked order.
+    pub fn list(mut self, list: StoryList, ids: &[u64]) -> Self {
+        self.dump = self.dump.list(list, ids);
+        self
+    }
+
+    /// Sets the latest item id, which defaults to the highest id in the dataset.
+    pub fn max_item(mut self, id: u64) -> Self {
+        self.dump = self.dump.max_item(id);
+        self
+    }
+
+    /// Sets the recently changed items and profiles.
+    pub fn updates(mut self, items: &[u64], profiles: &[&str]) -> Self {
+        self.updates = (
+            items.to_vec(),
+            profiles.iter().map(|name| name.to_string()).collect(),
+        );
+        self
+    }
+
+    /// Returns the status and JSON body for a path below the API version.
+    ///
+    /// Firebase paths answer `null` if there is nothing, Algolia items are not found.
+    fn respond(&self, path: &str) -> (u16, String) {
+        let path = path.trim_start_matches('/');
+        if let Some(id) = path.strip_prefix("api/v1/items/") {
+            return match id.parse().ok().and_then(|id| self.node(id)) {
+                Some(node) => (200, to_string(&node)),
+                None => (404, "{\"status\":404,\"error\":\"Not Found\"}".to_string()),
+            };
+        }
+        if path != "updates.json" {
+            return (200, self.dump.respond(path));
+        }
+        let (items, profiles) = &self.updates;
+        let body = format!(
+            "{{\"items\":{},\"profiles\":{}}}",
+            to_string(items),
+            to_string(profiles)
+        );
+        (200, body)
+    }
+
+    /// Returns the item with the id and everything below it, as Algolia does.
+    fn node(&self, id: u64) -> Option<RawNode> {
+        let item: Item = from_str(self.dump.item_json(id)?).ok()?;
+        let nodes = |ids: Option<Vec<u64>>| -> Vec<RawNode> {
+            ids.unwrap_or_default()
+                .into_iter()
+                .filter_map(|id| self.node(id))
+                .collect()
+        };
+        Some(RawNode {
+            id,
+            author: item.by.filter(|_| !item.deleted.unwrap_or(false)),
+            created_at_i: item.time,
+            title: item.title,
+            url: item.url,
+            text: item.text,
+            points: item.score,
+            parent_id: item.parent.or(item.poll),
+            children: Some(nodes(item.kids)),
+            options: item.parts.map(|parts| nodes(Some(parts))),
+            kind: item.kind,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+enum FaultKind {
+    Latency(Duration),
+    Status(u16),
+    Null,
+    Malformed,
+}
+
+/// A failure injected into the responses of a [MockServer].
+///
+/// Faults apply to every path and request unless narrowed down with
+/// [path](Fault::path) and [times](Fault::times).
+#[derive(Debug, Clone)]
+pub struct Fault {
+    kind: FaultKind,
+    path: Option<String>,
+    remaining: Option<usize>,
+}
+
+impl Fault {
+    fn new(kind: FaultKind) -> Self {
+        Self {
+            kind,
+            path: None,
+            remaining: None,
+        }
+    }
+
+    /// Delays the response, combining with other faults.
+    pub fn latency(delay: Duration) -> Self {
+        Self::new(FaultKind::Latency(delay))
+    }
+
+    /// Responds with an HTTP status code, such as 503.
+    pub fn status(code: u16) -> Self {
+        Self::new(FaultKind::Status(code))
+    }
+
+    /// Responds with `null`, as Firebase does for missing data.
+    pub fn null() -> Self {
+        Self::new(FaultKind::Null)
+    }
+
+    /// Responds with a body that is not valid JSON.
+    pub fn malformed() -> Self {
+        Self::new(FaultKind::Malformed)
+    }
+
+    /// Only applies to one path, such as `item/1.json`, relative to the base URL.
+    pub fn path(mut self, path: &str) -> Self {
+        self.path = Some(path.trim_start_matches('/').to_string());
+        self
+    }
+
+    /// Only applies to this many requests.
+    pub fn times(mut self, times: usize) -> Self {
+        self.remaining = Some(times);
+        self
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        self.remaining != Some(0)
+            && self
+                .path
+                .as_deref()
+                .is_none_or(|only| only == path.trim_start_matches('/'))
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    dataset: Dataset,
+    faults: Vec<Fault>,
+    requests: HashMap<String, u64>,
+}
+
+/// An HTTP server on a local port serving a [Dataset] like the Hacker News API.
+///
+/// The server stops when dropped.
+#[derive(Debug)]
+pub struct MockServer {
+    address: SocketAddr,
+    state: Arc<Mutex<State>>,
+    requests: Arc<AtomicU64>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Starts serving the dataset on a free local port.
+    pub fn start(dataset: Dataset) -> Result<Self, HError> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State {
+            dataset,
+            ..State::default()
+        }));
+        let requests = Arc::new(AtomicU64::new(0));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let (state, requests, stop) = (state.clone(), requests.clone(), stop.clone());
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let Ok(stream) = stream else {
+                        continue;
+                    };
+                    let (state, requests) = (state.clone(), requests.clone());
+                    thread::spawn(move || {
+                        let _ = handle(stream, &state, &requests);
+                    });
+                }
+            })
+        };
+
+        Ok(Self {
+            address,
+            state,
+            requests,
+            stop,
+            handle: Some(handle),
+        })
+    }
+
+    /// Returns the base URL to pass to [Client::with_base_url].
+    pub fn base_url(&self) -> String {
+        format!("http://{}/v0", self.address)
+    }
+
+    /// Returns the base URL to pass to [Client::with_search_url].
+    ///
+    /// Only the Algolia items endpoint is served, not searches.
+    pub fn search_url(&self) -> String {
+        format!("http://{}/api/v1", self.address)
+    }
+
+    /// Returns a new client using this server for both APIs.
+    pub fn client(&self) -> Client {
+        Client::new()
+            .with_base_url(&self.base_url())
+            .with_search_url(&self.search_url())
+    }
+
+    /// Injects a fault into later responses.
+    pub fn inject(&self, fault: Fault) {
+        self.state.lock().unwrap().faults.push(fault);
+    }
+
+    /// Removes all injected faults.
+    pub fn clear_faults(&self) {
+        self.state.lock().unwrap().faults.clear();
+    }
+
+    /// Changes the served data, for example to simulate new items.
+    pub fn update<F: FnOnce(Dataset) -> Dataset>(&self, f: F) {
+        let mut state = self.state.lock().unwrap();
+        let dataset = std::mem::take(&mut state.dataset);
+        state.dataset = f(dataset);
+    }
+
+    /// Returns the total amount of requests served.
+    pub fn requests(&self) -> u64 {
+        self.requests.load(Ordering::Relaxed)
+    }
+
+    /// Returns how often a path, such as `item/1.json`, was requested.
+    pub fn requests_to(&self, path: &str) -> u64 {
+        let state = self.state.lock().unwrap();
+        let path = path.trim_start_matches('/');
+        state.requests.get(path).copied().unwrap_or(0)
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // Wake up the accepting thread so it notices the stop flag.
+        let _ = TcpStream::connect(self.address);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn handle(stream: TcpStream, state: &Mutex<State>, requests: &AtomicU64) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 2 {
+        line.clear();
+    }
+
+    let target = request.split_whitespace().nth(1).unwrap_or("/");
+    let target = target.split('?').next().unwrap_or(target);
+    let path = target
+        .strip_prefix("/v0")
+        .unwrap_or(target)
+        .trim_start_matches('/')
+        .to_string();
+    requests.fetch_add(1, Ordering::Relaxed);
+
+    let (delay, status, body) = {
+        let mut state = state.lock().unwrap();
+        *state.requests.entry(path.clone()).or_insert(0) += 1;
+        let mut delay = Duration::ZERO;
+        let mut outcome = None;
+        for fault in state.faults.iter_mut().filter(|fault| fault.matches(&path)) {
+            let applied = match &fault.kind {
+                FaultKind::Latency(latency) => {
+                    delay += *latency;
+                    true
+                }
+                _ if outcome.is_some() => false,
+                FaultKind::Status(code) => {
+                    outcome = Some((*code, "{\"error\":\"injected\"}".to_string()));
+                    true
+                }
+                FaultKind::Null => {
+                    outcome = Some((200, "null".to_string()));
+                    true
+                }
+                FaultKind::Malformed => {
+                    outcome = Some((200, "{\"id\": 1, \"type\": ".to_string()));
+                    true
+                }
+            };
+            if applied {
+                if let Some(remaining) = &mut fault.remaining {
+                    *remaining -= 1;
+                }
+            }
+        }
+        let (status, body) = outcome.unwrap_or_else(|| state.dataset.respond(&path));
+        (delay, status, body)
+    };
+
+    thread::sleep(delay);
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        if status < 400 { "OK" } else { "Error" },
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
@@ -0,0 +1,573 @@
+//! Decoding of the HTML subset used in Hacker News text fields.
+//!
+//! Hacker News only emits a handful of tags in [Comment](crate::Comment) text, [Ask](crate::Ask) and
+//! [Poll](crate::Poll) bodies and [User](crate::User) profiles: `<p>`, `<i>`, `<a href>` and
+//! `<pre><code>`, plus character entities such as `&#x27;`. This module parses that subset
+//! into a small [Document] which can then be rendered as plain text, Markdown or sanitized HTML.
+
+use crate::permalink::{WebHost, WebRef, DEFAULT_WEB_HOST};
+
+/// A parsed piece of Hacker News text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Document {
+    /// The blocks making up the text, in order.
+    pub blocks: Vec<Block>,
+}
+
+/// A block level element of a [Document].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    /// A paragraph of inline content.
+    Paragraph(Vec<Inline>),
+    /// A preformatted code block, with entities already decoded.
+    Code(String),
+}
+
+/// An inline element inside a [Block::Paragraph].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inline {
+    /// Plain text, with entities already decoded.
+    Text(String),
+    /// Italic text.
+    Italic(Vec<Inline>),
+    /// A hyperlink with its anchor content.
+    Link {
+        /// The decoded target of the link.
+        href: String,
+        /// The anchor content of the link.
+        children: Vec<Inline>,
+    },
+}
+
+/// A link referenced from Hacker News text.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    /// The absolute, decoded URL of the link.
+    pub url: String,
+    /// The anchor text of the link, or the URL itself for bare references.
+    pub text: String,
+    /// The id of the Hacker News item this link points to, if any.
+    pub item: Option<u64>,
+}
+
+enum Frame {
+    Italic,
+    Link(String),
+}
+
+impl Document {
+    /// Parses Hacker News HTML into a document.
+    ///
+    /// Unknown tags are dropped while keeping their content, so this never fails.
+    pub fn parse(html: &str) -> Self {
+        Parser::default().parse(html)
+    }
+
+    /// Returns every link in the document, in order of appearance.
+    ///
+    /// Besides anchors this also picks up bare `news.ycombinator.com/item?id=` references
+    /// in the text itself. Relative links are resolved against the Hacker News website.
+    pub fn links(&self) -> Vec<Link> {
+        let mut links = vec![];
+        for block in &self.blocks {
+            match block {
+                Block::Paragraph(inlines) => collect_links(inlines, &mut links),
+                Block::Code(code) => bare_item_links(code, &mut links),
+            }
+        }
+        links
+    }
+
+    /// Renders the document as plain text, with paragraphs separated by blank lines.
+    pub fn to_plain(&self) -> String {
+        let blocks: Vec<String> = self
+            .blocks
+            .iter()
+            .map(|block| match block {
+                Block::Paragraph(inlines) => {
+                    let mut out = String::new();
+                    plain_inlines(inlines, &mut out);
+                    out
+                }
+                Block::Code(code) => code.clone(),
+            })
+            .collect();
+        blocks.join("\n\n")
+    }
+
+    /// Renders the document as CommonMark.
+    pub fn to_markdown(&self) -> String {
+        let blocks: Vec<String> = self
+            .blocks
+            .iter()
+            .map(|block| match block {
+                Block::Paragraph(inlines) => {
+                    let mut out = String::new();
+                    markdown_inlines(inlines, &mut out);
+                    out
+                }
+                Block::Code(code) => {
+                    let mut fence = String::from("```");
+                    while code.contains(&fence) {
+                        fence.push('`');
+                    }
+                    format!("{}\n{}\n{}", fence, code, fence)
+                }
+            })
+            .collect();
+        blocks.join("\n\n")
+    }
+
+    /// Renders the document as HTML containing only the tags Hacker News itself allows.
+    ///
+    /// All text is escaped and links with a scheme other than `http`, `https` or `mailto`
+    /// are rendered as their anchor text only.
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        for block in &self.blocks {
+            match block {
+                Block::Paragraph(inlines) => {
+                    out.push_str("<p>");
+                    html_inlines(inlines, &mut out);
+                    out.push_str("</p>");
+                }
+                Block::Code(code) => {
+                    out.push_str("<pre><code>");
+                    out.push_str(&escape_html(code));
+                    out.push_str("</code></pre>");
+                }
+            }
+        }
+        out
+    }
+}
+
+#[derive(Default)]
+struct Parser {
+    blocks: Vec<Block>,
+    stack: Vec<(Frame, Vec<Inline>)>,
+    current: Vec<Inline>,
+}
+
+impl Parser {
+    fn parse(mut self, html: &str) -> Document {
+        let mut rest = html;
+        while !rest.is_empty() {
+            let Some(start) = rest.find('<') else {
+                self.text(rest);
+                break;
+            };
+            self.text(&rest[..start]);
+            rest = &rest[start..];
+            let Some(end) = tag_end(rest) else {
+                self.text(rest);
+                break;
+            };
+            let tag = Tag::parse(&rest[1..end]);
+            rest = &rest[end + 1..];
+
+            match (tag.name.as_str(), tag.closing) {
+                ("p", _) => self.paragraph(),
+                ("pre", false) => {
+                    self.paragraph();
+                    let (code, remaining) = match rest.find("</pre>") {
+                        Some(end) => (&rest[..end], &rest[end + "</pre>".len()..]),
+                        None => (rest, ""),
+                    };
+                    rest = remaining;
+                    let code = code.replace("<code>", "").replace("</code>", "");
+                    let code = decode_entities(code.trim_end_matches('\n'));
+                    self.blocks.push(Block::Code(code));
+                }
+                ("i" | "em", false) => self.open(Frame::Italic),
+                ("i" | "em", true) => self.close(|frame| matches!(frame, Frame::Italic)),
+                ("a", false) => {
+                    let href = tag.href.unwrap_or_default();
+                    self.open(Frame::Link(href));
+                }
+                ("a", true) => self.close(|frame| matches!(frame, Frame::Link(_))),
+                _ => {}
+            }
+        }
+        self.paragraph();
+        Document {
+            blocks: self.blocks,
+        }
+    }
+
+    fn text(&mut self, raw: &str) {
+        if raw.is_empty() {
+            return;
+        }
+        let decoded = decode_entities(raw);
+        let mut collapsed = String::with_capacity(decoded.len());
+        for c in decoded.chars() {
+            if c.is_whitespace() {
+                if !collapsed.ends_with(' ') {
+                    collapsed.push(' ');
+                }
+            } else {
+                collapsed.push(c);
+            }
+        }
+        match self.current.last_mut() {
+            Some(Inline::Text(text)) => {
+                if text.ends_with(' ') && collapsed.starts_with(' ') {
+                    collapsed.remove(0);
+                }
+                text.push_str(&collapsed);
+            }
+            _ => self.current.push(Inline::Text(collapsed)),
+        }
+    }
+
+    fn open(&mut self, frame: Frame) {
+        let outer = std::mem::take(&mut self.current);
+        self.stack.push((frame, outer));
+    }
+
+    fn close(&mut self, matches: impl Fn(&Frame) -> bool) {
+        if !self.stack.iter().any(|(frame, _)| matches(frame)) {
+            return;
+        }
+        while let Some((frame, outer)) = self.stack.pop() {
+            let found = matches(&frame);
+            self.fold(frame, outer);
+            if found {
+                break;
+            }
+        }
+    }
+
+    fn fold(&mut self, frame: Frame, outer: Vec<Inline>) {
+        let children = std::mem::replace(&mut self.current, outer);
+        let inline = match frame {
+            Frame::Italic => Inline::Italic(children),
+            Frame::Link(href) => Inline::Link { href, children },
+        };
+        self.current.push(inline);
+    }
+
+    fn paragraph(&mut self) {
+        while let Some((frame, outer)) = self.stack.pop() {
+            self.fold(frame, outer);
+        }
+        let mut inlines = std::mem::take(&mut self.current);
+        trim_inlines(&mut inlines);
+        if !inlines.is_empty() {
+            self.blocks.push(Block::Paragraph(inlines));
+        }
+    }
+}
+
+struct Tag {
+    name: String,
+    closing: bool,
+    href: Option<String>,
+}
+
+impl Tag {
+    fn parse(inner: &str) -> Self {
+        let inner = inner.trim();
+        let (closing, inner) = match inner.strip_prefix('/') {
+            Some(inner) => (true, inner),
+            None => (false, inner),
+        };
+        let name_end = inner
+            .find(|c: char| c.is_whitespace() || c == '/')
+            .unwrap_or(inner.len());
+        let name = inner[..name_end].to_ascii_lowercase();
+        let href = attribute(&inner[name_end..], "href").map(|value| decode_entities(&value));
+        Tag {
+            name,
+            closing,
+            href,
+        }
+    }
+}
+
+/// Finds the `>` ending the tag at the start of `html`, skipping quoted attribute values.
+fn tag_end(html: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, c) in html.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), c) if c == open => quote = None,
+            (None, '>') => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn attribute(attributes: &str, wanted: &str) -> Option<String> {
+    let mut rest = attributes.trim_start();
+    while !rest.is_empty() {
+        let name_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let name = &rest[..name_end];
+        rest = rest[name_end..].trim_start();
+        let value = if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            match after.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let end = after[1..].find(quote).map_or(after.len(), |i| i + 1);
+                    rest = after.get(end + 1..).unwrap_or("");
+                    &after[1..end]
+                }
+                _ => {
+                    let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                    rest = &after[end..];
+                    &after[..end]
+                }
+            }
+        } else {
+            ""
+        };
+        if name.eq_ignore_ascii_case(wanted) {
+            return Some(value.to_string());
+        }
+        rest = rest.trim_start();
+    }
+    None
+}
+
+fn trim_inlines(inlines: &mut Vec<Inline>) {
+    if let Some(Inline::Text(text)) = inlines.first_mut() {
+        *text = text.trim_start().to_string();
+    }
+    if let Some(Inline::Text(text)) = inlines.last_mut() {
+        *text = text.trim_end().to_string();
+    }
+    inlines.retain(|inline| !matches!(inline, Inline::Text(text) if text.is_empty()));
+}
+
+/// Decodes the named and numeric character references used by Hacker News.
+///
+/// Unknown or malformed references are left untouched.
+pub fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| decode_entity(&rest[1..end]).map(|c| (c, end)));
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    if let Some(numeric) = entity.strip_prefix('#') {
+        let code = match numeric.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => numeric.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+    match entity {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => None,
+    }
+}
+
+pub(crate) fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#x27;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Escapes the characters Markdown would read as emphasis, code, links or HTML.
+pub fn escape_markdown(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '>') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn inline_text(inlines: &[Inline]) -> String {
+    let mut out = String::new();
+    plain_inlines(inlines, &mut out);
+    out
+}
+
+fn plain_inlines(inlines: &[Inline], out: &mut String) {
+    for inline in inlines {
+        match inline {
+            Inline::Text(text) => out.push_str(text),
+            Inline::Italic(children) => plain_inlines(children, out),
+            Inline::Link { href, children } => {
+                let text = inline_text(children);
+                if text.is_empty() || text == *href {
+                    out.push_str(href);
+                } else {
+                    out.push_str(&format!("{} ({})", text, href));
+                }
+            }
+        }
+    }
+}
+
+fn collect_links(inlines: &[Inline], links: &mut Vec<Link>) {
+    for inline in inlines {
+        match inline {
+            Inline::Text(text) => bare_item_links(text, links),
+            Inline::Italic(children) => collect_links(children, links),
+            Inline::Link { href, children } => {
+                let url = absolute_url(href);
+                links.push(Link {
+                    item: item_id(&url),
+                    text: inline_text(children),
+                    url,
+                });
+            }
+        }
+    }
+}
+
+const ITEM_REFERENCE: &str = "news.ycombinator.com/item?id=";
+
+fn bare_item_links(text: &str, links: &mut Vec<Link>) {
+    let mut rest = text;
+    while let Some(start) = rest.find(ITEM_REFERENCE) {
+        let prefix = ["https://www.", "http://www.", "https://", "http://", "www."]
+            .iter()
+            .find(|prefix| rest[..start].ends_with(*prefix))
+            .map_or(0, |prefix| prefix.len());
+        let after = &rest[start + ITEM_REFERENCE.len()..];
+        let digits = after
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(after.len());
+        if let Ok(id) = after[..digits].parse() {
+            let end = start + ITEM_REFERENCE.len() + digits;
+            links.push(Link {
+                url: format!("{}/item?id={}", DEFAULT_WEB_HOST, id),
+                text: rest[start - prefix..end].to_string(),
+                item: Some(id),
+            });
+        }
+        rest = &after[digits..];
+    }
+}
+
+fn absolute_url(href: &str) -> String {
+    if href.starts_with("//") {
+        format!("https:{}", href)
+    } else if scheme(href).is_some() {
+        href.to_string()
+    } else if href.starts_with('/') {
+        format!("{}{}", DEFAULT_WEB_HOST, href)
+    } else {
+        format!("{}/{}", DEFAULT_WEB_HOST, href)
+    }
+}
+
+fn item_id(url: &str) -> Option<u64> {
+    match WebHost::default().parse(url)? {
+        WebRef::Item(item) => Some(item.id),
+        _ => None,
+    }
+}
+
+fn markdown_inlines(inlines: &[Inline], out: &mut String) {
+    for inline in inlines {
+        match inline {
+            Inline::Text(text) => out.push_str(&escape_markdown(text)),
+            Inline::Italic(children) => {
+                out.push('*');
+                markdown_inlines(children, out);
+                out.push('*');
+            }
+            Inline::Link { href, children } => {
+                let text = inline_text(children);
+                let target = href
+                    .replace(' ', "%20")
+                    .replace('(', "%28")
+                    .replace(')', "%29")
+                    .replace('<', "%3C")
+                    .replace('>', "%3E");
+                if text.is_empty() || text == *href {
+                    out.push_str(&format!("<{}>", target));
+                } else {
+                    out.push('[');
+                    markdown_inlines(children, out);
+                    out.push_str(&format!("]({})", target));
+                }
+            }
+        }
+    }
+}
+
+fn html_inlines(inlines: &[Inline], out: &mut String) {
+    for inline in inlines {
+        match inline {
+            Inline::Text(text) => out.push_str(&escape_html(text)),
+            Inline::Italic(children) => {
+                out.push_str("<i>");
+                html_inlines(children, out);
+                out.push_str("</i>");
+            }
+            Inline::Link { href, children } => {
+                if is_safe_href(href) {
+                    out.push_str(&format!(
+                        "<a href=\"{}\" rel=\"nofollow\">",
+                        escape_html(href)
+                    ));
+                    html_inlines(children, out);
+                    out.push_str("</a>");
+                } else {
+                    html_inlines(children, out);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the lower-cased scheme of an absolute href, such as `javascript`.
+fn scheme(href: &str) -> Option<String> {
+    let scheme = &href[..href.find(':')?];
+    let is_scheme = !scheme.contains(['/', '?', '#']);
+    is_scheme.then(|| scheme.to_ascii_lowercase())
+}
+
+fn is_safe_href(href: &str) -> bool {
+    match scheme(href) {
+        Some(scheme) => matches!(scheme.as_str(), "http" | "https" | "mailto"),
+        None => true,
+    }
+}
@@ -0,0 +1,164 @@
+//! Comment trees of whole discussions.
+
+use crate::prelude::*;
+use std::collections::HashMap;
+
+/// A submission together with its whole tree of comments.
+#[derive(Debug)]
+pub struct Thread {
+    /// The item the discussion belongs to, usually a [Story], [Ask] or [Poll].
+    pub root: AnyItem,
+    /// The top-level comments, in the order Hacker News ranks them.
+    pub comments: Vec<CommentNode>,
+}
+
+/// A comment in a [Thread] with its replies.
+#[derive(Debug)]
+pub struct CommentNode {
+    /// The id of the comment.
+    pub id: u64,
+    /// The comment itself, or [None] if it was deleted.
+    pub comment: Option<Comment>,
+    /// The replies to this comment, in ranked order.
+    pub children: Vec<CommentNode>,
+}
+
+impl CommentNode {
+    /// Returns whether the comment was deleted.
+    pub fn is_deleted(&self) -> bool {
+        self.comment.is_none()
+    }
+
+    /// Returns the amount of comments below this one, at any depth.
+    pub fn descendants(&self) -> usize {
+        self.children
+            .iter()
+            .map(|child| 1 + child.descendants())
+            .sum()
+    }
+}
+
+impl Thread {
+    /// Returns the total amount of comments in the thread, including deleted ones.
+    pub fn len(&self) -> usize {
+        self.comments
+            .iter()
+            .map(|comment| 1 + comment.descendants())
+            .sum()
+    }
+
+    /// Returns whether the thread has no comments.
+    pub fn is_empty(&self) -> bool {
+        self.comments.is_empty()
+    }
+
+    /// Returns every comment depth-first, together with its depth starting at 0.
+    pub fn walk(&self) -> Vec<(usize, &CommentNode)> {
+        fn visit<'a>(
+            nodes: &'a [CommentNode],
+            depth: usize,
+            out: &mut Vec<(usize, &'a CommentNode)>,
+        ) {
+            for node in nodes {
+                out.push((depth, node));
+                visit(&node.children, depth + 1, out);
+            }
+        }
+        let mut out = vec![];
+        visit(&self.comments, 0, &mut out);
+        out
+    }
+}
+
+/// Builds a thread level by level, fetching the items of each level with `fetch`.
+///
+/// Items that do not exist are kept as deleted comments.
+pub(crate) fn build<F>(root: Item, fetch: F) -> Result<Thread, HError>
+where
+    F: Fn(&[u64]) -> Vec<Result<Item, HError>>,
+{
+    let top: Vec<u64> = root.kids.clone().unwrap_or_default();
+    let root: AnyItem = root.try_into()?;
+    let mut items = fetch_tree(&top, fetch)?;
+
+    fn assemble(
+        ids: &[u64],
+        items: &mut HashMap<u64, Option<Item>>,
+    ) -> Result<Vec<CommentNode>, HError> {
+        let mut nodes = vec![];
+        for id in ids {
+            let Some(item) = items.remove(id) else {
+                continue;
+            };
+            let kids = item
+                .as_ref()
+                .and_then(|item| item.kids.clone())
+                .unwrap_or_default();
+            let comment = match item {
+                Some(item) if !item.is_deleted() => Some(item.try_into()?),
+                _ => None,
+            };
+            nodes.push(CommentNode {
+                id: *id,
+                comment,
+                children: assemble(&kids, items)?,
+            });
+        }
+        Ok(nodes)
+    }
+
+    Ok(Thread {
+        root,
+        comments: assemble(&top, &mut items)?,
+    })
+}
+
+/// Fetches the same items as [build], returning them raw with the root first and the comments
+/// depth-first.
+///
+/// Items that do not exist are left out.
+pub(crate) fn items<F>(root: Item, fetch: F) -> Result<Vec<Item>, HError>
+where
+    F: Fn(&[u64]) -> Vec<Result<Item, HError>>,
+{
+    let top: Vec<u64> = root.kids.clone().unwrap_or_default();
+    let mut items = fetch_tree(&top, fetch)?;
+
+    fn visit(ids: &[u64], items: &mut HashMap<u64, Option<Item>>, out: &mut Vec<Item>) {
+        for id in ids {
+            if let Some(Some(item)) = items.remove(id) {
+                let kids = item.kids.clone().unwrap_or_default();
+                out.push(item);
+                visit(&kids, items, out);
+            }
+        }
+    }
+
+    let mut out = vec![root];
+    visit(&top, &mut items, &mut out);
+    Ok(out)
+}
+
+/// Fetches every comment below `top` level by level, keyed by id and [None] if it does not exist.
+fn fetch_tree<F>(top: &[u64], fetch: F) -> Result<HashMap<u64, Option<Item>>, HError>
+where
+    F: Fn(&[u64]) -> Vec<Result<Item, HError>>,
+{
+    let mut items: HashMap<u64, Option<Item>> = HashMap::new();
+    let mut level = top.to_vec();
+    while !level.is_empty() {
+        let mut next = vec![];
+        for (id, result) in level.iter().zip(fetch(&level)) {
+            let item = match result {
+                Err(HError::NotFound) => None,
+                result => Some(result?),
+            };
+            if let Some(kids) = item.as_ref().and_then(|item| item.kids.as_ref()) {
+                next.extend(kids.iter().filter(|kid| !items.contains_key(kid)));
+            }
+            items.insert(*id, item);
+        }
+        level = next;
+    }
+    Ok(items)
+}
@@ -1,5 +1,6 @@
 //! User struct and associated methods.
 
+use crate::parallel::{self, DEFAULT_THREADS};
 use crate::prelude::*;
 use miniserde::{Deserialize, Serialize};
 
@@ -48,4 +49,51 @@ impl User {
         }
         Ok(stories)
     }
+
+    /// Returns every item this user has submitted, whatever its kind, fetched concurrently.
+    pub fn submissions(&self, client: &Client) -> Result<Vec<AnyItem>, HError> {
+        parallel::map(&self.submitted, DEFAULT_THREADS, |id| client.get_any(*id))
+            .into_iter()
+            .collect()
+    }
+
+    /// Returns the profile description decoded to plain text.
+    pub fn about_plain(&self) -> String {
+        Document::parse(&self.about).to_plain()
+    }
+
+    /// Returns the profile description converted to Markdown.
+    pub fn about_markdown(&self) -> String {
+        Document::parse(&self.about).to_markdown()
+    }
+
+    /// Returns the profile description as sanitized HTML.
+    pub fn about_html(&self) -> String {
+        Document::parse(&self.about).to_html()
+    }
+
+    /// Returns the links referenced in the profile description.
+    pub fn links(&self) -> Vec<Link> {
+        Document::parse(&self.about).links()
+    }
+
+    /// Returns the profile page of this user on the Hacker News website.
+    pub fn permalink(&self) -> String {
+        self.permalink_on(&WebHost::default())
+    }
+
+    /// Returns the profile page of this user on a different web host.
+    pub fn permalink_on(&self, host: &WebHost) -> String {
+        host.user(&self.id)
+    }
+
+    /// Returns the page listing the submissions of this user on the Hacker News website.
+    pub fn submitted_permalink(&self) -> String {
+        self.submitted_permalink_on(&WebHost::default())
+    }
+
+    /// Returns the page listing the submissions of this user on a different web host.
+    pub fn submitted_permalink_on(&self, host: &WebHost) -> String {
+        host.submitted(&self.id)
+    }
 }
@@ -0,0 +1,227 @@
+//! Polling watcher emitting events for new items, updates and ranking changes.
+
+use crate::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::ops::ControlFlow;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// A change noticed by a [Watcher].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// A new item was posted.
+    NewItem(u64),
+    /// An existing item changed, for example its score or comment count.
+    ItemChanged(u64),
+    /// The profile of a [User] changed.
+    ProfileChanged(String),
+    /// The top stories changed.
+    TopListChanged {
+        /// Ids that entered the list.
+        entered: Vec<u64>,
+        /// Ids that left the list.
+        left: Vec<u64>,
+        /// Ids that stayed in the list but changed position.
+        moved: Vec<Move>,
+    },
+}
+
+/// An item changing position in a list, with zero-based positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    /// The id of the item.
+    pub id: u64,
+    /// The previous position.
+    pub from: usize,
+    /// The new position.
+    pub to: usize,
+}
+
+/// Polls the latest item, updates and top stories, reporting each change once.
+///
+/// The first poll only records the current state, later polls report what changed since.
+#[derive(Debug)]
+pub struct Watcher {
+    client: Client,
+    interval: Duration,
+    max_item: Option<u64>,
+    items: HashSet<u64>,
+    profiles: HashSet<String>,
+    top: Option<Vec<u64>>,
+}
+
+impl Watcher {
+    /// Creates a watcher polling every 30 seconds, bypassing the caches of the client.
+    pub fn new(client: &Client) -> Self {
+        Self {
+            client: client.uncached(),
+            interval: Duration::from_secs(30),
+            max_item: None,
+            items: HashSet::new(),
+            profiles: HashSet::new(),
+            top: None,
+        }
+    }
+
+    /// Sets the time between polls.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Polls once and returns the changes since the previous poll.
+    pub fn poll(&mut self) -> Result<Vec<Event>, HError> {
+        let max_item = self.client.get_latest()?;
+        let Update { items, profiles } = self.client.get_update()?;
+        let top = self.client.get_top()?;
+
+        let mut events = vec![];
+        let known = self.max_item.unwrap_or(max_item);
+        if let Some(previous) = self.max_item {
+            events.extend((previous + 1..=max_item).map(Event::NewItem));
+        }
+        self.max_item = Some(max_item.max(known));
+
+        let first = self.top.is_none();
+        for id in &items {
+            if !first && *id <= known && !self.items.contains(id) {
+                events.push(Event::ItemChanged(*id));
+            }
+        }
+        self.items = items.into_iter().collect();
+
+        for name in &profiles {
+            if !first && !self.profiles.contains(name) {
+                events.push(Event::ProfileChanged(name.clone()));
+            }
+        }
+        self.profiles = profiles.into_iter().collect();
+
+        if let Some(previous) = self.top.replace(top) {
+            let current = self.top.as_deref().unwrap_or_default();
+            if let Some(event) = diff(&previous, current) {
+                events.push(event);
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Polls on the interval, passing every event to the callback until it breaks.
+    ///
+    /// Returns the first error encountered while polling.
+    pub fn run<F>(mut self, mut callback: F) -> Result<(), HError>
+    where
+        F: FnMut(Event) -> ControlFlow<()>,
+    {
+        loop {
+            for event in self.poll()? {
+                if callback(event).is_break() {
+                    return Ok(());
+                }
+            }
+            thread::sleep(self.interval);
+        }
+    }
+
+    /// Polls on a background thread, sending events and errors to the returned [Events].
+    ///
+    /// Polling continues after errors and stops once the [Events] is dropped.
+    pub fn spawn(mut self) -> Events {
+        let (sender, receiver) = mpsc::channel();
+        let alive = Arc::new(());
+        let watching = Arc::downgrade(&alive);
+        thread::spawn(move || {
+            while watching.strong_count() > 0 {
+                let sent = match self.poll() {
+                    Ok(events) => events
+                        .into_iter()
+                        .all(|event| sender.send(Ok(event)).is_ok()),
+                    Err(e) => sender.send(Err(e)).is_ok(),
+                };
+                if !sent {
+                    break;
+                }
+                thread::sleep(self.interval);
+            }
+        });
+        Events {
+            receiver,
+            _alive: alive,
+        }
+    }
+}
+
+/// The events of a [Watcher] polling on a background thread, see [Watcher::spawn].
+///
+/// Iterating blocks until the next event. Dropping it stops the thread after the current poll.
+#[derive(Debug)]
+pub struct Events {
+    receiver: Receiver<Result<Event, HError>>,
+    _alive: Arc<()>,
+}
+
+impl Events {
+    /// Returns the next event without blocking, if there is one.
+    pub fn try_recv(&self) -> Option<Result<Event, HError>> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Waits up to `timeout` for the next event.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<Result<Event, HError>> {
+        self.receiver.recv_timeout(timeout).ok()
+    }
+}
+
+impl Iterator for Events {
+    type Item = Result<Event, HError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.recv().ok()
+    }
+}
+
+fn diff(previous: &[u64], current: &[u64]) -> Option<Event> {
+    let before: HashMap<u64, usize> = previous
+        .iter()
+        .enumerate()
+        .map(|(position, id)| (*id, position))
+        .collect();
+    let after: HashMap<u64, usize> = current
+        .iter()
+        .enumerate()
+        .map(|(position, id)| (*id, position))
+        .collect();
+
+    let entered: Vec<u64> = current
+        .iter()
+        .filter(|id| !before.contains_key(id))
+        .copied()
+        .collect();
+    let left: Vec<u64> = previous
+        .iter()
+        .filter(|id| !after.contains_key(id))
+        .copied()
+        .collect();
+    let moved: Vec<Move> = current
+        .iter()
+        .enumerate()
+        .filter_map(|(to, id)| {
+            let from = *before.get(id)?;
+            (from != to).then_some(Move { id: *id, from, to })
+        })
+        .collect();
+
+    if entered.is_empty() && left.is_empty() && moved.is_empty() {
+        None
+    } else {
+        Some(Event::TopListChanged {
+            entered,
+            left,
+            moved,
+        })
+    }
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "testing")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::stories;
+    use hnews::builder::StoryBuilder;
+    use hnews::cache::{CacheConfig, CacheStats};
+    use std::time::Duration;
+
+    #[test]
+    fn test_cache_lru_and_stats() {
+        let server = stories(1..=3);
+        let client = server.client().with_cache(CacheConfig {
+            capacity: 2,
+            ..CacheConfig::default()
+        });
+
+        client.get_item(1).unwrap();
+        client.get_item(2).unwrap();
+        client.get_item(1).unwrap();
+        client.get_item(3).unwrap();
+        client.get_item(1).unwrap();
+        assert_eq!(server.requests_to("item/1.json"), 1);
+
+        client.get_item(2).unwrap();
+        assert_eq!(server.requests_to("item/2.json"), 2);
+        assert_eq!(
+            client.cache_stats(),
+            Some(CacheStats {
+                hits: 2,
+                misses: 4,
+                evictions: 2,
+                entries: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_cache_ttl_and_bypass() {
+        let server = stories(1..=3);
+        let client = server.client().with_cache(CacheConfig {
+            item_ttl: Duration::from_millis(50),
+            ..CacheConfig::default()
+        });
+
+        client.get_item(1).unwrap();
+        client.get_item(1).unwrap();
+        assert_eq!(server.requests_to("item/1.json"), 1);
+        std::thread::sleep(Duration::from_millis(100));
+        client.get_item(1).unwrap();
+        assert_eq!(server.requests_to("item/1.json"), 2);
+
+        server.update(|data| data.item(&StoryBuilder::new(1).score(42).item()));
+        assert_eq!(client.get_story(1).unwrap().score, 1);
+        assert_eq!(client.uncached().get_story(1).unwrap().score, 42);
+        assert_eq!(server.requests_to("item/1.json"), 3);
+        assert_eq!(client.get_story(1).unwrap().score, 42);
+        assert_eq!(server.requests_to("item/1.json"), 3);
+    }
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "testing")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::temp_path;
+    use hnews::builder::{CommentBuilder, StoryBuilder, ThreadBuilder, UserBuilder};
+    use hnews::cassette::Cassette;
+    use hnews::prelude::*;
+    use hnews::testing::{Dataset, MockServer};
+
+    #[test]
+    fn test_cassette_record_replay() {
+        let path = temp_path("cassette.jsonl");
+        let thread = ThreadBuilder::new(StoryBuilder::new(1).by("pg"))
+            .reply(1, CommentBuilder::new(2, 1).by("tptacek"))
+            .reply(1, CommentBuilder::new(3, 1).by("pg"))
+            .deleted(2, 4);
+        let user = UserBuilder::new("pg").submitted(&[3, 1]).build();
+        let server = MockServer::start(Dataset::new().thread(&thread).user(&user)).unwrap();
+
+        let client = server
+            .client()
+            .with_cassette(Cassette::record(&path).unwrap());
+        let user = client.get_user("pg").unwrap();
+        let comments = client.get_story(1).unwrap().comments(&client).unwrap();
+        let submissions = user.submissions(&client).unwrap();
+        assert!(matches!(client.get_item(5), Err(HError::NotFound)));
+        let recorded = client.cassette().unwrap().paths();
+        assert_eq!(recorded.len(), 5);
+        assert!(recorded.contains(&"user/pg.json".to_string()));
+        drop(client);
+        drop(server);
+
+        let client = Client::new()
+            .with_base_url("http://127.0.0.1:9/v0")
+            .with_cassette(Cassette::replay(&path).unwrap());
+        let user = client.get_user("pg").unwrap();
+        let replayed = client.get_story(1).unwrap().comments(&client).unwrap();
+        assert_eq!(replayed.len(), comments.len());
+        assert_eq!(replayed[0].by, "tptacek");
+        assert_eq!(user.submissions(&client).unwrap().len(), submissions.len());
+        assert!(matches!(client.get_item(5), Err(HError::NotFound)));
+        assert!(matches!(
+            client.get_item(4),
+            Err(HError::Unrecorded(path)) if path == "item/4.json"
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
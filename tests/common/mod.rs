@@ -0,0 +1,52 @@
+//! Fixtures shared by the integration tests.
+
+#![allow(dead_code)]
+
+#[cfg(feature = "testing")]
+use hnews::builder::StoryBuilder;
+use hnews::prelude::*;
+#[cfg(feature = "testing")]
+use hnews::testing::{Dataset, MockServer};
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+/// Parses raw item JSON.
+pub fn item(json: &str) -> Item {
+    miniserde::json::from_str(json).unwrap()
+}
+
+/// Parses raw item JSON into one of the typed structs.
+pub fn parse<T: TryFrom<Item, Error = HError>>(json: &str) -> T {
+    item(json).try_into().unwrap()
+}
+
+/// Reads the raw item of a fixture file, such as `tests/story.json`.
+pub fn load_item(file: &str) -> Item {
+    item(&std::fs::read_to_string(file).unwrap())
+}
+
+/// Reads a fixture file, such as `tests/story.json`, into one of the typed structs.
+pub fn load<T: TryFrom<Item, Error = HError>>(file: &str) -> T {
+    load_item(file).try_into().unwrap()
+}
+
+/// Returns a path in the temporary directory unique to this test process, such as
+/// `hnews-<pid>-search.jsonl`, removing whatever an earlier run left there.
+pub fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("hnews-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_dir_all(&path);
+    path
+}
+
+/// Builds a story for each id in the range.
+#[cfg(feature = "testing")]
+pub fn story_items(ids: RangeInclusive<u64>) -> Vec<Item> {
+    ids.map(|id| StoryBuilder::new(id).item()).collect()
+}
+
+/// Starts a mock server serving a story for each id in the range.
+#[cfg(feature = "testing")]
+pub fn stories(ids: RangeInclusive<u64>) -> MockServer {
+    MockServer::start(Dataset::new().items(&story_items(ids))).unwrap()
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "testing")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{stories, temp_path};
+    use hnews::crawler::Crawler;
+    use hnews::prelude::*;
+    use hnews::testing::Fault;
+
+    fn ids(items: &[Item]) -> Vec<u64> {
+        let mut ids: Vec<u64> = items.iter().map(|item| item.id()).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn test_crawler_resume_retries_failed() {
+        let server = stories(0..=5);
+        let path = temp_path("crawler-failed.checkpoint");
+        server.inject(Fault::status(500).path("item/3.json"));
+
+        let mut items: Vec<Item> = vec![];
+        let crawler = Crawler::new(&server.client(), 1..=5)
+            .retries(0)
+            .checkpoint(&path);
+        let report = crawler.run(&mut items, |_| {}).unwrap();
+        assert_eq!(report.failed, vec![3]);
+        assert_eq!(ids(&items), vec![1, 2, 4, 5]);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "done\nfailed 3\n");
+
+        server.clear_faults();
+        let mut items: Vec<Item> = vec![];
+        let report = crawler.run(&mut items, |_| {}).unwrap();
+        assert!(report.failed.is_empty());
+        assert_eq!(report.progress.resumed, 5);
+        assert_eq!(ids(&items), vec![3]);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "done\n");
+        assert_eq!(server.requests_to("item/1.json"), 1);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_crawler_resume_skips_delivered() {
+        let server = stories(0..=5);
+        let path = temp_path("crawler-delivered.checkpoint");
+        std::fs::write(&path, "next 2\ndelivered 4\nfailed 1\n").unwrap();
+
+        let mut items: Vec<Item> = vec![];
+        let report = Crawler::new(&server.client(), 1..=5)
+            .threads(2)
+            .checkpoint(&path)
+            .run(&mut items, |_| {})
+            .unwrap();
+        assert_eq!(ids(&items), vec![1, 2, 3, 5]);
+        assert_eq!(report.progress.resumed, 2);
+        assert_eq!(report.progress.done, 5);
+        assert_eq!(server.requests_to("item/4.json"), 0);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "done\n");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_crawler_range_edges() {
+        let server = stories(0..=5);
+        let path = temp_path("crawler-edges.checkpoint");
+        let crawler = Crawler::new(&server.client(), 0..=2)
+            .backwards()
+            .checkpoint(&path);
+        let mut items: Vec<Item> = vec![];
+        crawler.run(&mut items, |_| {}).unwrap();
+        crawler.run(&mut items, |_| {}).unwrap();
+        assert_eq!(ids(&items), vec![0, 1, 2]);
+        assert_eq!(server.requests_to("item/0.json"), 1);
+        std::fs::remove_file(&path).unwrap();
+
+        let report = Crawler::new(&server.client(), u64::MAX - 1..=u64::MAX)
+            .run(&mut items, |_| {})
+            .unwrap();
+        assert_eq!(report.progress.total, 2);
+        assert_eq!(report.progress.missing, 2);
+    }
+
+    #[test]
+    fn test_crawler_tiny_rate_limit() {
+        let server = stories(0..=5);
+        let mut items: Vec<Item> = vec![];
+        Crawler::new(&server.client(), 1..=2)
+            .rate_limit(1e-300)
+            .run(&mut items, |_| {})
+            .unwrap();
+        assert_eq!(ids(&items), vec![1, 2]);
+    }
+}
@@ -0,0 +1,48 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::load;
+    use hnews::csv::CsvWriter;
+    use hnews::prelude::*;
+
+    #[test]
+    fn test_csv_quoting_and_columns() {
+        let story: Story = load("tests/story.json");
+        let mut writer = CsvWriter::new(vec![])
+            .columns(&["id", "title", "domain"])
+            .unwrap();
+        writer.write(&story).unwrap();
+        let csv = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert_eq!(
+            csv,
+            "id,title,domain\r\n8863,My YC app: Dropbox - Throw away your USB drive,getdropbox.com\r\n"
+        );
+
+        let comment: Comment = load("tests/comment.json");
+        let mut writer = CsvWriter::new(vec![])
+            .columns(&["by", "text"])
+            .unwrap()
+            .plain_text();
+        writer.write(&comment).unwrap();
+        let csv = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert_eq!(
+            csv,
+            "by,text\r\nnorvig,\"Aw shucks, guys ... you make me blush with your compliments.\n\nTell you what, Ill make a deal: I'll keep writing if you keep reading. K?\"\r\n"
+        );
+
+        assert!(matches!(
+            CsvWriter::<Vec<u8>, Comment>::new(vec![]).columns(&["by", "title"]),
+            Err(HError::UnknownColumn(column)) if column == "title"
+        ));
+    }
+
+    #[test]
+    fn test_csv_header_without_records() {
+        let writer = CsvWriter::<Vec<u8>, Story>::new(vec![])
+            .columns(&["id", "title"])
+            .unwrap();
+        let csv = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert_eq!(csv, "id,title\r\n");
+    }
+}
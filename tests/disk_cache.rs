@@ -0,0 +1,112 @@
+#![cfg(feature = "testing")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::temp_path;
+    use hnews::builder::{StoryBuilder, UserBuilder};
+    use hnews::disk_cache::{DiskCache, DiskCacheConfig};
+    use hnews::testing::{Dataset, MockServer};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    #[test]
+    fn test_disk_cache_roundtrip() {
+        let dir = temp_path("disk-cache-roundtrip");
+        let server = MockServer::start(
+            Dataset::new()
+                .item(&StoryBuilder::new(1).item())
+                .item(&StoryBuilder::new(2).time(now()).item()),
+        )
+        .unwrap();
+
+        let client = server
+            .client()
+            .with_disk_cache(DiskCache::open(&dir).unwrap());
+        client.get_item(1).unwrap();
+        client.get_item(2).unwrap();
+
+        let client = server
+            .client()
+            .with_disk_cache(DiskCache::open(&dir).unwrap());
+        assert_eq!(client.get_story(1).unwrap().title, "Story 1");
+        assert_eq!(server.requests_to("item/1.json"), 1);
+        client.get_item(2).unwrap();
+        assert_eq!(server.requests_to("item/2.json"), 2);
+
+        let mut names: Vec<_> = std::fs::read_dir(dir.join("items/0"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["1.json", "2.json"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_disk_cache_user_expiry_and_invalidation() {
+        let dir = temp_path("disk-cache-expiry");
+        let server =
+            MockServer::start(Dataset::new().user(&UserBuilder::new("pg").karma(1).build()))
+                .unwrap();
+        let client = server.client().with_disk_cache(
+            DiskCache::with_config(
+                &dir,
+                DiskCacheConfig {
+                    user_max_age: Duration::from_millis(50),
+                    ..DiskCacheConfig::default()
+                },
+            )
+            .unwrap(),
+        );
+
+        client.get_user("pg").unwrap();
+        client.get_user("pg").unwrap();
+        assert_eq!(server.requests_to("user/pg.json"), 1);
+        std::thread::sleep(Duration::from_millis(100));
+        client.get_user("pg").unwrap();
+        assert_eq!(server.requests_to("user/pg.json"), 2);
+
+        server.update(|data| data.user(&UserBuilder::new("pg").karma(2).build()));
+        assert_eq!(client.get_user("pg").unwrap().karma, 1);
+        client.invalidate_user("pg");
+        assert_eq!(client.get_user("pg").unwrap().karma, 2);
+        assert_eq!(server.requests_to("user/pg.json"), 3);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_disk_cache_case_distinct_users() {
+        let dir = temp_path("disk-cache-case");
+        let server = MockServer::start(
+            Dataset::new()
+                .user(&UserBuilder::new("Foo_Bar").karma(1).build())
+                .user(&UserBuilder::new("foo_bar").karma(2).build()),
+        )
+        .unwrap();
+        let client = server
+            .client()
+            .with_disk_cache(DiskCache::open(&dir).unwrap());
+
+        client.get_user("Foo_Bar").unwrap();
+        client.get_user("foo_bar").unwrap();
+        assert_eq!(client.get_user("Foo_Bar").unwrap().karma, 1);
+        assert_eq!(client.get_user("foo_bar").unwrap().karma, 2);
+        assert_eq!(server.requests(), 2);
+
+        let mut names: Vec<_> = std::fs::read_dir(dir.join("users"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["_foo___bar.json", "foo__bar.json"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,66 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::temp_path;
+    use hnews::dump::Dump;
+    use hnews::prelude::*;
+    use std::fs;
+
+    const LINES: &str = r#"{"id":1,"type":"story","by":"pg","time":1,"title":"Hello","url":"https://example.com","score":5,"descendants":2,"kids":[2]}
+{"id":2,"type":"comment","by":"sama","time":2,"parent":1,"text":"First","kids":[3]}
+{"id":3,"type":"comment","by":"pg","time":3,"parent":2,"text":"Second"}
+{"id":"pg","created":1,"karma":155,"about":"","submitted":[160704,3,1]}
+
+{"id":160704,"type":"poll","by":"pg","time":4,"title":"Ban Valleywag?","text":"","score":10,"descendants":0,"parts":[160705]}
+"#;
+
+    #[test]
+    fn test_dump_offline() {
+        let dir = temp_path("dump");
+        fs::create_dir_all(dir.join("items")).unwrap();
+        fs::write(dir.join("dump.jsonl"), LINES).unwrap();
+        fs::copy("tests/polloption.json", dir.join("items/160705.json")).unwrap();
+        fs::write(dir.join("README.md"), "not a record").unwrap();
+
+        let dump = Dump::load_dir(&dir).unwrap().list(StoryList::Top, &[1]);
+        assert_eq!((dump.items(), dump.users()), (5, 1));
+        let client = Client::new()
+            .with_base_url("http://127.0.0.1:9/v0")
+            .with_dump(dump);
+
+        let story = client.get_story(client.get_top().unwrap()[0]).unwrap();
+        assert_eq!(story.by(&client).unwrap().karma, 155);
+        let comments = story.comments(&client).unwrap();
+        assert_eq!(comments[0].by, "sama");
+        assert_eq!(comments[0].replies(&client).unwrap()[0].text, "Second");
+
+        let option = client.get_poll_option(160705).unwrap();
+        let poll = option.poll(&client).unwrap();
+        assert_eq!(poll.options(&client).unwrap()[0].score, 335);
+        assert_eq!(
+            poll.by(&client)
+                .unwrap()
+                .submissions(&client)
+                .unwrap()
+                .len(),
+            3
+        );
+
+        assert_eq!(client.get_latest().unwrap(), 160705);
+        assert!(client.get_new().unwrap().is_empty());
+        assert!(client.get_updates().unwrap().is_empty());
+        assert!(matches!(client.get_item(4), Err(HError::NotFound)));
+        assert!(matches!(
+            client.subscribe(hnews::stream::Path::Item(1)),
+            Err(HError::Unrecorded(_))
+        ));
+
+        let mut dump = Dump::load(dir.join("dump.jsonl")).unwrap();
+        assert!(matches!(
+            dump.insert_json("[1, 2]"),
+            Err(HError::ConversionFailed)
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
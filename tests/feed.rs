@@ -0,0 +1,29 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::load;
+    use hnews::feed::Feed;
+    use hnews::prelude::*;
+
+    #[test]
+    fn test_feed_rss_and_atom() {
+        let stories: [Story; 1] = [load("tests/story.json")];
+        let feed = Feed::new("Best & brightest").stories(&stories);
+
+        let rss = feed.to_rss();
+        assert!(rss.contains("<title>Best &amp; brightest</title>"));
+        assert!(rss.contains("<link>http://www.getdropbox.com/u/2/screencast.html</link>"));
+        assert!(rss.contains("<comments>https://news.ycombinator.com/item?id=8863</comments>"));
+        assert!(rss.contains("<pubDate>Wed, 04 Apr 2007 19:16:40 +0000</pubDate>"));
+        assert!(rss.contains("<description>111 points by dhouston | 71 comments</description>"));
+
+        let atom = feed.to_atom();
+        assert!(atom.contains("<updated>2007-04-04T19:16:40Z</updated>"));
+        assert!(atom.contains("<name>dhouston</name>"));
+
+        let filtered = Feed::new("Popular").stories(&stories).min_score(200);
+        assert_eq!(filtered.entries().count(), 0);
+        assert!(!filtered.to_rss().contains("<item>"));
+    }
+}
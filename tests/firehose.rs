@@ -0,0 +1,49 @@
+#![cfg(feature = "testing")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::stories;
+    use hnews::firehose::Firehose;
+    use hnews::prelude::*;
+    use hnews::testing::{Fault, MockServer};
+    use std::time::Duration;
+
+    fn firehose(server: &MockServer) -> Firehose {
+        Firehose::starting_at(&server.client(), 1)
+            .retry_delay(Duration::from_millis(10))
+            .max_attempts(3)
+            .threads(4)
+    }
+
+    #[test]
+    fn test_firehose_retries_in_order() {
+        let server = stories(1..=4);
+        server.inject(Fault::null().path("item/2.json").times(2));
+        server.inject(Fault::status(503).path("item/3.json").times(1));
+
+        let ids: Vec<u64> = firehose(&server)
+            .take(4)
+            .map(|item| item.unwrap().id())
+            .collect();
+        assert_eq!(ids, vec![1, 2, 3, 4]);
+        assert_eq!(server.requests_to("item/2.json"), 3);
+        assert_eq!(server.requests_to("item/3.json"), 2);
+    }
+
+    #[test]
+    fn test_firehose_pairs_errors_with_ids() {
+        let server = stories(1..=4);
+        server.inject(Fault::status(503).path("item/2.json").times(3));
+        server.inject(Fault::malformed().path("item/3.json").times(1));
+
+        let results: Vec<_> = firehose(&server).take(4).collect();
+        assert_eq!(results[0].as_ref().unwrap().id(), 1);
+        assert!(matches!(results[1], Err((2, HError::UReq(_)))));
+        assert!(matches!(results[2], Err((3, HError::Miniserde(_)))));
+        assert_eq!(results[3].as_ref().unwrap().id(), 4);
+        assert_eq!(server.requests_to("item/2.json"), 3);
+        assert_eq!(server.requests_to("item/3.json"), 1);
+    }
+}
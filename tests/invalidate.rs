@@ -0,0 +1,74 @@
+#![cfg(feature = "testing")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::stories;
+    use hnews::builder::{StoryBuilder, UserBuilder};
+    use hnews::cache::CacheConfig;
+    use hnews::invalidate::{Invalidated, Invalidation};
+    use hnews::testing::MockServer;
+
+    fn server() -> MockServer {
+        let server = stories(1..=3);
+        server.update(|data| {
+            data.user(&UserBuilder::new("pg").karma(1).build())
+                .updates(&[1, 3], &["pg"])
+        });
+        server
+    }
+
+    fn change(server: &MockServer) {
+        server.update(|data| {
+            data.item(&StoryBuilder::new(1).score(42).item())
+                .user(&UserBuilder::new("pg").karma(2).build())
+        });
+    }
+
+    #[test]
+    fn test_apply_updates_evict() {
+        let server = server();
+        let client = server.client().with_cache(CacheConfig::default());
+        client.get_item(1).unwrap();
+        client.get_item(2).unwrap();
+        client.get_user("pg").unwrap();
+        change(&server);
+
+        assert_eq!(
+            client.apply_updates(Invalidation::Evict).unwrap(),
+            Invalidated {
+                items: vec![1, 3],
+                profiles: vec!["pg".to_string()],
+            }
+        );
+        assert_eq!(server.requests_to("item/1.json"), 1);
+        assert_eq!(server.requests_to("item/3.json"), 0);
+
+        assert_eq!(client.get_story(1).unwrap().score, 42);
+        assert_eq!(client.get_user("pg").unwrap().karma, 2);
+        client.get_item(2).unwrap();
+        assert_eq!(server.requests_to("item/1.json"), 2);
+        assert_eq!(server.requests_to("user/pg.json"), 2);
+        assert_eq!(server.requests_to("item/2.json"), 1);
+    }
+
+    #[test]
+    fn test_apply_updates_refresh() {
+        let server = server();
+        let client = server.client().with_cache(CacheConfig::default());
+        client.get_item(1).unwrap();
+        client.get_user("pg").unwrap();
+        change(&server);
+
+        client.apply_updates(Invalidation::Refresh).unwrap();
+        assert_eq!(server.requests_to("item/1.json"), 2);
+        assert_eq!(server.requests_to("user/pg.json"), 2);
+        assert_eq!(server.requests_to("item/3.json"), 0);
+
+        assert_eq!(client.get_story(1).unwrap().score, 42);
+        assert_eq!(client.get_user("pg").unwrap().karma, 2);
+        assert_eq!(server.requests_to("item/1.json"), 2);
+        assert_eq!(server.requests_to("user/pg.json"), 2);
+    }
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "testing")]
+
+#[cfg(test)]
+mod tests {
+    use hnews::builder::{CommentBuilder, StoryBuilder, ThreadBuilder, UserBuilder};
+    use hnews::cache::CacheConfig;
+    use hnews::crawler::Crawler;
+    use hnews::prelude::*;
+    use hnews::testing::{Dataset, Fault, MockServer};
+
+    fn dataset() -> Dataset {
+        Dataset::new()
+            .item_json(&std::fs::read_to_string("tests/story.json").unwrap())
+            .unwrap()
+            .item_json(
+                r#"{"id":1,"type":"comment","by":"pg","time":1,"parent":8863,"text":"First"}"#,
+            )
+            .unwrap()
+            .user_json(r#"{"id":"pg","created":1,"karma":2,"about":"","submitted":[1]}"#)
+            .unwrap()
+            .list(StoryList::Top, &[8863])
+            .updates(&[1], &["pg"])
+    }
+
+    #[test]
+    fn test_mock_client() {
+        let server = MockServer::start(dataset()).unwrap();
+        let client = server.client();
+
+        assert_eq!(client.get_story(8863).unwrap().score, 111);
+        assert_eq!(client.get_user("pg").unwrap().karma, 2);
+        assert_eq!(client.get_top().unwrap(), vec![8863]);
+        assert_eq!(client.get_latest().unwrap(), 8863);
+        assert_eq!(client.get_updates().unwrap(), vec![1]);
+        assert!(matches!(client.get_item(2), Err(HError::NotFound)));
+        assert!(matches!(client.get_user("nobody"), Err(HError::NotFound)));
+
+        server.inject(Fault::status(503).path("item/1.json").times(1));
+        assert!(matches!(client.get_item(1), Err(HError::UReq(_))));
+        assert!(client.get_comment(1).is_ok());
+
+        server.inject(Fault::null().path("item/1.json").times(1));
+        assert!(matches!(client.get_item(1), Err(HError::NotFound)));
+
+        server.inject(Fault::malformed().times(1));
+        assert!(matches!(client.get_item(1), Err(HError::Miniserde(_))));
+        assert_eq!(server.requests_to("item/1.json"), 4);
+    }
+
+    #[test]
+    fn test_mock_cache() {
+        let server = MockServer::start(dataset()).unwrap();
+        let client = server.client().with_cache(CacheConfig::default());
+        client.get_item(1).unwrap();
+        client.get_item(1).unwrap();
+        assert_eq!(server.requests_to("item/1.json"), 1);
+
+        client.invalidate_item(1);
+        client.get_item(1).unwrap();
+        assert_eq!(server.requests_to("item/1.json"), 2);
+    }
+
+    #[test]
+    fn test_mock_crawler_retries() {
+        let server = MockServer::start(dataset().max_item(8863)).unwrap();
+        server.inject(Fault::status(500).path("item/1.json").times(2));
+        let mut items: Vec<Item> = vec![];
+        let report = Crawler::new(&server.client(), 1..=3)
+            .threads(2)
+            .run(&mut items, |_| {})
+            .unwrap();
+        assert_eq!(report.progress.done, 3);
+        assert_eq!(report.progress.missing, 2);
+        assert!(report.failed.is_empty());
+        assert_eq!(items.len(), 1);
+        assert_eq!(server.requests_to("item/1.json"), 3);
+    }
+
+    #[test]
+    fn test_builders_seed_dataset() {
+        let builder = ThreadBuilder::new(StoryBuilder::new(1).title("Hello").score(10))
+            .reply(1, CommentBuilder::new(2, 1).by("alice").text("Hi"))
+            .deleted(2, 3)
+            .generate(2, 2);
+        let thread = builder.build();
+        assert_eq!(thread.len(), 2 + 2 + 4);
+        assert!(thread.comments[0].children[0].is_deleted());
+
+        let user = UserBuilder::new("alice").karma(42).submitted(&[2]).build();
+        let server = MockServer::start(Dataset::new().thread(&builder).user(&user)).unwrap();
+        let client = server.client();
+        let fetched = client.get_thread(1).unwrap();
+        assert_eq!(fetched.len(), thread.len());
+        assert_eq!(client.get_story(1).unwrap().comments, 8);
+        assert_eq!(client.get_user("alice").unwrap().karma, 42);
+        assert!(matches!(
+            &user.submissions(&client).unwrap()[..],
+            [AnyItem::Comment(comment)] if comment.text == "Hi"
+        ));
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use hnews::permalink::{ItemRef, UserRef, WebHost, WebRef, DEFAULT_WEB_HOST};
+
+    #[test]
+    fn test_roundtrip() {
+        let host = WebHost::default();
+        assert_eq!(
+            host.parse(&host.comment(8863, 8952)),
+            Some(WebRef::Item(ItemRef {
+                id: 8863,
+                comment: Some(8952)
+            }))
+        );
+        assert_eq!(
+            host.parse("http://www.news.ycombinator.com/submitted?id=pg"),
+            Some(WebRef::Submitted(UserRef { name: "pg".into() }))
+        );
+        assert_eq!(host.parse("https://example.com/item?id=1"), None);
+
+        let clone = WebHost::new("http://localhost:8080/hn/");
+        assert_eq!(clone.user("pg"), "http://localhost:8080/hn/user?id=pg");
+        assert_eq!(
+            clone.parse("http://localhost:8080/hn/from?site=github.com/user"),
+            Some(WebRef::Site("github.com/user".into()))
+        );
+
+        assert_eq!(
+            host.user("a&b c"),
+            format!("{}/user?id=a%26b%20c", DEFAULT_WEB_HOST)
+        );
+        assert_eq!(
+            host.parse(&host.submitted("a&b c")),
+            Some(WebRef::Submitted(UserRef {
+                name: "a&b c".into()
+            }))
+        );
+        assert_eq!(
+            host.from_site("example.com/a?b#c"),
+            format!("{}/from?site=example.com/a%3Fb%23c", DEFAULT_WEB_HOST)
+        );
+        assert_eq!(
+            host.parse(&host.from_site("example.com/a?b#c")),
+            Some(WebRef::Site("example.com/a?b#c".into()))
+        );
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_site_permalink_on() {
+        use hnews::builder::StoryBuilder;
+
+        let story = StoryBuilder::new(1)
+            .url("https://github.com/rust-lang/rust")
+            .build();
+        let clone = WebHost::new("http://localhost:8080");
+        assert_eq!(
+            story.site_permalink_on(&clone).as_deref(),
+            Some("http://localhost:8080/from?site=github.com/rust-lang")
+        );
+        assert_eq!(
+            story.site_permalink().as_deref(),
+            Some("https://news.ycombinator.com/from?site=github.com/rust-lang")
+        );
+    }
+}
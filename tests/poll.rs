@@ -0,0 +1,31 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::parse;
+    use hnews::prelude::*;
+
+    fn option(id: u64, score: i64, text: &str) -> PollOption {
+        parse(&format!(
+            r#"{{"by":"pg","id":{},"poll":1,"score":{},"text":"{}","time":0,"type":"pollopt"}}"#,
+            id, score, text
+        ))
+    }
+
+    #[test]
+    fn test_results() {
+        let results = PollResults::new(vec![
+            option(2, 1, "No"),
+            option(3, 3, "Yes"),
+            option(4, 1, "Maybe"),
+        ]);
+        assert_eq!(results.total, 5);
+        assert_eq!(results.leader().unwrap().option.id, 3);
+        let ranks: Vec<usize> = results.options.iter().map(|r| r.rank).collect();
+        assert_eq!(ranks, vec![2, 1, 2]);
+        assert_eq!(
+            results.render_bars(10),
+            "No    ██         1 ( 20.0%)\nYes   ██████     3 ( 60.0%)\nMaybe ██         1 ( 20.0%)\n"
+        );
+    }
+}
@@ -0,0 +1,69 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::parse;
+    use hnews::prelude::*;
+
+    fn thread() -> Thread {
+        let comment = |json| Some(parse(json));
+        Thread {
+            root: parse(
+                r#"{"id":1,"type":"story","by":"pg","time":1175714200,"title":"Hello","score":5,"descendants":3,"text":"Is <i>this</i> on?"}"#,
+            ),
+            comments: vec![
+                CommentNode {
+                    id: 2,
+                    comment: comment(
+                        r#"{"id":2,"type":"comment","by":"sama","time":1175714260,"parent":1,"text":"Yes &amp; no"}"#,
+                    ),
+                    children: vec![CommentNode {
+                        id: 4,
+                        comment: comment(
+                            r#"{"id":4,"type":"comment","by":"troll","time":1175714320,"parent":2,"text":"No","dead":true}"#,
+                        ),
+                        children: vec![],
+                    }],
+                },
+                CommentNode {
+                    id: 3,
+                    comment: None,
+                    children: vec![],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_render_thread() {
+        let thread = thread();
+
+        let markdown = thread.to_markdown();
+        assert!(markdown.starts_with("# [Hello](https://news.ycombinator.com/item?id=1)\n"));
+        assert!(markdown.contains("Is *this* on?"));
+        assert!(markdown.contains("> **sama** · [2007-04-04 19:17 UTC](https://news.ycombinator.com/item?id=2)\n>\n> Yes & no\n"));
+        assert!(markdown.contains("> > **troll** [dead] · "));
+        assert!(markdown.contains("\n> *[deleted]*\n"));
+
+        let html = thread.to_html();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<p>Yes &amp; no</p>"));
+        assert!(html.contains("<li class=\"comment dead\"><details open>"));
+        assert!(html.contains("<li class=\"deleted\">"));
+
+        let plain = thread.to_plain();
+        assert!(plain.contains("\nsama - 2007-04-04 19:17 UTC\nYes & no\n|\n|   troll [dead] - "));
+        assert!(plain.ends_with("\n[deleted]\n"));
+    }
+
+    #[test]
+    fn test_render_escapes_markdown_title() {
+        let mut thread = thread();
+        thread.root = parse(
+            r#"{"id":1,"type":"story","by":"pg","time":1175714200,"title":"Why [x](y) and *z*","score":5,"descendants":0}"#,
+        );
+        assert!(thread.to_markdown().starts_with(
+            "# [Why \\[x\\](y) and \\*z\\*](https://news.ycombinator.com/item?id=1)\n"
+        ));
+    }
+}
@@ -0,0 +1,57 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::temp_path;
+    use hnews::cassette::Cassette;
+    use hnews::prelude::*;
+    use hnews::search::{Search, Tag};
+
+    const PATH: &str = "search_by_date?query=rust%20lang&tags=story%2C%28author_pg%2Cauthor_sama%29&numericFilters=created_at_i%3E%3D1700000000%2Cpoints%3E%3D100&page=1&hitsPerPage=3";
+
+    const BODY: &str = r#"{"hits":[{"created_at":"2023-11-14T22:13:20Z","title":"Rust Lang","url":"https://www.rust-lang.org","author":"pg","points":120,"story_text":null,"comment_text":null,"num_comments":40,"story_id":null,"story_title":null,"parent_id":null,"created_at_i":1700000000,"_tags":["story","author_pg","story_1"],"objectID":"1","_highlightResult":{"title":{"value":"<em>Rust</em> Lang","matchLevel":"full"}}},{"title":"Ask HN: Rust?","url":null,"author":"sama","points":100,"story_text":"Why <i>rust</i>?","num_comments":3,"created_at_i":1700000100,"_tags":["story","author_sama","story_2","ask_hn"],"objectID":"2"},{"author":"pg","points":null,"comment_text":"Rust is great","num_comments":null,"story_id":1,"story_title":"Rust Lang","parent_id":1,"created_at_i":1700000200,"_tags":["comment","author_pg","story_1"],"objectID":"3"}],"nbHits":7,"page":1,"nbPages":3,"hitsPerPage":3,"processingTimeMS":1,"query":"rust lang","params":""}"#;
+
+    #[test]
+    fn test_search() {
+        let path = temp_path("search.jsonl");
+        let body = BODY.replace('"', "\\\"");
+        std::fs::write(
+            &path,
+            format!("{{\"path\":\"{}\",\"body\":\"{}\"}}\n", PATH, body),
+        )
+        .unwrap();
+        let client = Client::new()
+            .with_search_url("http://127.0.0.1:9/api/v1/")
+            .with_cassette(Cassette::replay(&path).unwrap());
+
+        let search = Search::new("rust lang")
+            .by_date()
+            .tag(Tag::Story)
+            .any_tag(&[
+                Tag::Author("pg".to_string()),
+                Tag::Author("sama".to_string()),
+            ])
+            .since(1_700_000_000)
+            .min_points(100)
+            .page(1)
+            .hits_per_page(3);
+        let results = client.search(&search).unwrap();
+        assert_eq!((results.total, results.page, results.pages), (7, 1, 3));
+        assert!(results.has_next());
+
+        assert!(matches!(&results.hits[0].item, AnyItem::Story(story) if story.score == 120));
+        assert!(matches!(&results.hits[1].item, AnyItem::Ask(ask) if ask.comments == 3));
+        let AnyItem::Comment(comment) = &results.hits[2].item else {
+            panic!("expected a comment");
+        };
+        assert_eq!((comment.id, comment.parent), (3, 1));
+        assert_eq!(comment.text, "Rust is great");
+        assert_eq!(results.hits[2].story_title.as_deref(), Some("Rust Lang"));
+
+        assert!(matches!(
+            client.search(&Search::new("rust")),
+            Err(HError::Unrecorded(path)) if path == "search?query=rust"
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
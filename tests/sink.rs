@@ -0,0 +1,35 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::load_item;
+    use hnews::prelude::*;
+    use hnews::sink::{JsonLines, JsonLinesReader, Record, Sink};
+
+    #[test]
+    fn test_json_lines_roundtrip() {
+        let mut sink = JsonLines::new(vec![]);
+        for file in ["tests/story.json", "tests/comment.json"] {
+            sink.write_item(&load_item(file)).unwrap();
+        }
+        let user: User = miniserde::json::from_str(
+            r#"{"id":"pg","created":1160418092,"karma":155111,"about":"","submitted":[1]}"#,
+        )
+        .unwrap();
+        sink.write_user(&user).unwrap();
+        let dump = sink.into_inner().unwrap();
+        assert_eq!(dump.iter().filter(|b| **b == b'\n').count(), 3);
+
+        let records: Vec<Record> = JsonLinesReader::new(dump.as_slice())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert!(matches!(&records[2], Record::User(user) if user.id == "pg"));
+
+        let items: Vec<AnyItem> = JsonLinesReader::new(dump.as_slice())
+            .items()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert!(matches!(&items[0], AnyItem::Story(story) if story.id == 8863));
+        assert!(matches!(&items[1], AnyItem::Comment(comment) if comment.id == 2921983));
+    }
+}
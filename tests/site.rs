@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+    use hnews::site::{domain, normalize, site_label};
+
+    #[test]
+    fn test_domain() {
+        assert_eq!(
+            domain("https://WWW.Example.com:8080/a?b#c").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(
+            domain("http://xn--mnchen-3ya.de/").as_deref(),
+            Some("münchen.de")
+        );
+        assert_eq!(domain(""), None);
+    }
+
+    #[test]
+    fn test_site_label() {
+        assert_eq!(
+            site_label("https://github.com/rust-lang/rust").as_deref(),
+            Some("github.com/rust-lang")
+        );
+        assert_eq!(
+            site_label("https://medium.com/@someone/post-123").as_deref(),
+            Some("medium.com/@someone")
+        );
+        assert_eq!(
+            site_label("https://medium.com/topic/post").as_deref(),
+            Some("medium.com")
+        );
+        assert_eq!(
+            site_label("https://blog.example.com/post").as_deref(),
+            Some("blog.example.com")
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize("http://www.example.com:80/post/?utm_source=hn&b=2&a=1#top"),
+            normalize("https://example.com/post?a=1&b=2")
+        );
+        assert_eq!(
+            normalize("https://example.com/index.html").as_deref(),
+            Some("https://example.com")
+        );
+        assert_eq!(
+            normalize("http://[::1]:8080/a/").as_deref(),
+            Some("https://[::1]:8080/a")
+        );
+    }
+}
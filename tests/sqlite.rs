@@ -0,0 +1,56 @@
+#![cfg(feature = "sqlite")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{item, temp_path};
+    use hnews::prelude::*;
+    use hnews::sink::Sink;
+    use hnews::sqlite::{SqliteSink, SqliteStore};
+
+    #[test]
+    fn test_sqlite_roundtrip() {
+        let path = temp_path("sqlite");
+
+        let mut sink = SqliteSink::open(&path).unwrap();
+        sink.write_item(&item(
+            r#"{"id":1,"type":"story","by":"pg","time":1,"title":"Old","url":"https://example.com","score":1,"descendants":0}"#,
+        ))
+        .unwrap();
+        sink.write_item(&item(
+            r#"{"id":1,"type":"story","by":"pg","time":1,"title":"Hello","url":"https://example.com","score":5,"descendants":2,"kids":[3,2]}"#,
+        ))
+        .unwrap();
+        sink.write_item(&item(
+            r#"{"id":2,"type":"comment","by":"sama","time":2,"text":"First","parent":1,"kids":[4]}"#,
+        ))
+        .unwrap();
+        sink.write_item(&item(
+            r#"{"id":3,"deleted":true,"type":"comment","time":3,"parent":1}"#,
+        ))
+        .unwrap();
+        sink.write_list("topstories", &[3, 2, 1]).unwrap();
+        sink.write_list("topstories", &[1]).unwrap();
+        sink.flush().unwrap();
+        drop(sink);
+
+        let store = SqliteStore::open(&path).unwrap();
+        let story = store.get_story(1).unwrap();
+        assert_eq!((story.title.as_str(), story.score), ("Hello", 5));
+        assert!(matches!(store.get_item(5), Err(HError::NotFound)));
+        assert_eq!(store.get_list("topstories").unwrap(), vec![1]);
+
+        let thread = store.thread(1).unwrap();
+        assert_eq!(thread.comments.len(), 2);
+        assert_eq!(thread.comments[0].id, 3);
+        assert!(thread.comments[0].is_deleted());
+        assert_eq!(thread.comments[1].children.len(), 1);
+        assert!(thread.comments[1].children[0].is_deleted());
+
+        let submissions = store.user_submissions("pg").unwrap();
+        assert!(matches!(&submissions[..], [AnyItem::Story(story)] if story.id == 1));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod tests {
+    use hnews::prelude::*;
+    use hnews::stream::{Path, Update};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serves one SSE response per connection, closing the connection after each.
+    fn serve(responses: Vec<&'static str>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for body in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                let mut stream = stream;
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n{}",
+                    body
+                )
+                .unwrap();
+            }
+        });
+        format!("http://{}/v0", address)
+    }
+
+    #[test]
+    fn test_subscribe_top_stories() {
+        let base = serve(vec![
+            "event: put\ndata: {\"path\":\"/\",\"data\":[3,2,1]}\n\n",
+            "event: keep-alive\ndata: null\n\nevent: put\ndata: {\"path\":\"/\",\"data\":[3,2,1]}\n\nevent: patch\ndata: {\"path\":\"/\",\"data\":{\"1\":4}}\n\nevent: cancel\ndata: null\n\n",
+        ]);
+        let client = Client::new().with_base_url(&base);
+        let updates: Vec<Vec<u64>> = client
+            .subscribe(Path::TopStories)
+            .unwrap()
+            .map(|update| match update.unwrap() {
+                Update::TopStories(ids) => ids,
+                other => panic!("unexpected update {:?}", other),
+            })
+            .collect();
+        assert_eq!(updates, vec![vec![3, 2, 1], vec![3, 2, 1], vec![3, 4, 1]]);
+    }
+}
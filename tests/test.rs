@@ -44,4 +44,44 @@ mod tests {
     fn test_story() {
         test!(Story, "tests/story.json");
     }
+
+    #[test]
+    fn test_raw_roundtrip() {
+        for file in [
+            "tests/ask.json",
+            "tests/comment.json",
+            "tests/job.json",
+            "tests/poll.json",
+            "tests/polloption.json",
+            "tests/story.json",
+        ] {
+            let s = std::fs::read_to_string(file).unwrap();
+            let item: Item = miniserde::json::from_str(&s).unwrap();
+            let json = miniserde::json::to_string(&item);
+            let any: AnyItem = item.try_into().unwrap();
+            assert_eq!(miniserde::json::to_string(&Item::from(any)), json);
+        }
+    }
+
+    #[test]
+    fn test_any_item_without_url_or_text() {
+        let ask = r#"{"by":"pg","descendants":0,"id":1,"score":1,"time":1,"title":"Ask HN","type":"story"}"#;
+        let item: Item = miniserde::json::from_str(ask).unwrap();
+        match item.try_into().unwrap() {
+            AnyItem::Ask(ask) => assert_eq!(ask.text, ""),
+            item => panic!("expected an ask, got {:?}", item),
+        }
+
+        let job =
+            r#"{"by":"pg","id":2,"score":1,"text":"Hiring","time":1,"title":"Job","type":"job"}"#;
+        let item: Item = miniserde::json::from_str(job).unwrap();
+        match item.try_into().unwrap() {
+            AnyItem::Job(job) => {
+                assert_eq!(job.url, None);
+                assert_eq!(job.text.as_deref(), Some("Hiring"));
+                assert_eq!(job.domain(), None);
+            }
+            item => panic!("expected a job, got {:?}", item),
+        }
+    }
 }
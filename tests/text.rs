@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use hnews::text::{escape_markdown, Block, Document, Inline};
+
+    const COMMENT: &str = "It&#x27;s <i>fine</i>.<p>See <a href=\"https:&#x2F;&#x2F;example.com&#x2F;a_b\" rel=\"nofollow\">the docs</a><p><pre><code>  let x = 1 &lt; 2;\n</code></pre>";
+
+    #[test]
+    fn test_parse() {
+        let doc = Document::parse(COMMENT);
+        assert_eq!(doc.blocks.len(), 3);
+        assert_eq!(
+            doc.blocks[0],
+            Block::Paragraph(vec![
+                Inline::Text("It's ".into()),
+                Inline::Italic(vec![Inline::Text("fine".into())]),
+                Inline::Text(".".into()),
+            ])
+        );
+        assert_eq!(doc.blocks[2], Block::Code("  let x = 1 < 2;".into()));
+    }
+
+    #[test]
+    fn test_plain() {
+        let doc = Document::parse(COMMENT);
+        assert_eq!(
+            doc.to_plain(),
+            "It's fine.\n\nSee the docs (https://example.com/a_b)\n\n  let x = 1 < 2;"
+        );
+    }
+
+    #[test]
+    fn test_markdown() {
+        let doc = Document::parse(COMMENT);
+        assert_eq!(
+            doc.to_markdown(),
+            "It's *fine*.\n\nSee [the docs](https://example.com/a_b)\n\n```\n  let x = 1 < 2;\n```"
+        );
+    }
+
+    #[test]
+    fn test_quoted_attributes() {
+        let doc = Document::parse("<a href=\"https://e.com/a>b\">t</a>");
+        let links: Vec<_> = doc
+            .links()
+            .into_iter()
+            .map(|link| (link.url, link.text))
+            .collect();
+        assert_eq!(
+            links,
+            vec![("https://e.com/a>b".to_string(), "t".to_string())]
+        );
+
+        let doc =
+            Document::parse("<a href=\"https://a.com/&lt;x&gt;\">https://a.com/&lt;x&gt;</a>");
+        assert_eq!(doc.to_markdown(), "<https://a.com/%3Cx%3E>");
+    }
+
+    #[test]
+    fn test_html() {
+        let doc = Document::parse("<a href=\"javascript:alert(1)\">x</a> <script>&lt;b&gt;");
+        assert_eq!(doc.to_html(), "<p>x &lt;b&gt;</p>");
+    }
+
+    #[test]
+    fn test_links() {
+        let doc = Document::parse(
+            "<a href=\"item?id=42\">parent</a>, see news.ycombinator.com/item?id=7 and <a href=\"https://example.com\">this</a> <a href=\"javascript:alert(1)\">x</a> <a href=\"mailto:pg@example.com\">pg</a>",
+        );
+        let links: Vec<_> = doc
+            .links()
+            .into_iter()
+            .map(|link| (link.url, link.text, link.item))
+            .collect();
+        assert_eq!(
+            links,
+            vec![
+                (
+                    "https://news.ycombinator.com/item?id=42".to_string(),
+                    "parent".to_string(),
+                    Some(42)
+                ),
+                (
+                    "https://news.ycombinator.com/item?id=7".to_string(),
+                    "news.ycombinator.com/item?id=7".to_string(),
+                    Some(7)
+                ),
+                ("https://example.com".to_string(), "this".to_string(), None),
+                ("javascript:alert(1)".to_string(), "x".to_string(), None),
+                ("mailto:pg@example.com".to_string(), "pg".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_escape_markdown() {
+        assert_eq!(
+            escape_markdown("Show HN: [a_b](c) *<d>* `e`"),
+            "Show HN: \\[a\\_b\\](c) \\*\\<d\\>\\* \\`e\\`"
+        );
+    }
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "testing")]
+
+#[cfg(test)]
+mod tests {
+    use hnews::builder::{CommentBuilder, StoryBuilder, ThreadBuilder};
+    use hnews::prelude::*;
+    use hnews::testing::{Dataset, Fault, MockServer};
+    use miniserde::json::to_string;
+
+    #[test]
+    fn test_algolia_thread() {
+        let thread = ThreadBuilder::new(StoryBuilder::new(1).title("Algolia"))
+            .generate(2, 3)
+            .deleted(2, 100)
+            .reply(100, CommentBuilder::new(101, 100).text("Orphan"));
+        let server = MockServer::start(Dataset::new().thread(&thread)).unwrap();
+        let client = server.client();
+
+        let firebase = client.get_thread(1).unwrap();
+        let requests = server.requests();
+        let algolia = client.get_algolia_thread(1).unwrap();
+        assert_eq!(server.requests(), requests + 1);
+        assert_eq!(algolia.len(), 16);
+        assert_eq!(algolia.to_plain(), firebase.to_plain());
+        assert_eq!(algolia.to_plain(), thread.build().to_plain());
+
+        server.inject(Fault::status(503).path("api/v1/items/1"));
+        let requests = server.requests();
+        let fallback = client.get_thread_at_once(1).unwrap();
+        assert_eq!(fallback.to_plain(), firebase.to_plain());
+        assert_eq!(server.requests(), requests + 1 + 1 + 16);
+
+        assert!(client.get_algolia_thread(2000).is_err());
+    }
+
+    #[test]
+    fn test_thread_items_stay_raw() {
+        let thread = ThreadBuilder::new(StoryBuilder::new(1).title("Raw"))
+            .reply(1, CommentBuilder::new(2, 1).text("First"))
+            .deleted(2, 3)
+            .reply(3, CommentBuilder::new(4, 3).text("Orphan"))
+            .reply(1, CommentBuilder::new(5, 1).text("Second"));
+        let server = MockServer::start(Dataset::new().thread(&thread)).unwrap();
+
+        let json = |items: &[Item]| items.iter().map(to_string).collect::<Vec<_>>();
+        let items = server.client().get_thread_items(1).unwrap();
+        assert_eq!(json(&items), json(&thread.items()));
+    }
+}
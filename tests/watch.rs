@@ -0,0 +1,69 @@
+#![cfg(feature = "testing")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{stories, story_items};
+    use hnews::prelude::*;
+    use hnews::watch::{Event, Move, Watcher};
+    use std::time::Duration;
+
+    #[test]
+    fn test_watcher_events() {
+        let server = stories(1..=3);
+        server.update(|data| data.list(StoryList::Top, &[1, 2, 3]).updates(&[1], &["pg"]));
+        let mut watcher = Watcher::new(&server.client());
+        assert_eq!(watcher.poll().unwrap(), vec![]);
+        assert_eq!(watcher.poll().unwrap(), vec![]);
+
+        server.update(|data| {
+            data.items(&story_items(4..=5))
+                .list(StoryList::Top, &[3, 1, 4])
+                .updates(&[1, 2, 4], &["pg", "sama"])
+        });
+        assert_eq!(
+            watcher.poll().unwrap(),
+            vec![
+                Event::NewItem(4),
+                Event::NewItem(5),
+                Event::ItemChanged(2),
+                Event::ProfileChanged("sama".to_string()),
+                Event::TopListChanged {
+                    entered: vec![4],
+                    left: vec![2],
+                    moved: vec![
+                        Move {
+                            id: 3,
+                            from: 2,
+                            to: 0
+                        },
+                        Move {
+                            id: 1,
+                            from: 0,
+                            to: 1
+                        },
+                    ],
+                },
+            ]
+        );
+        assert_eq!(watcher.poll().unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_spawned_watcher_stops_when_dropped() {
+        let server = stories(1..=3);
+        server.update(|data| data.list(StoryList::Top, &[1, 2, 3]));
+        let events = Watcher::new(&server.client())
+            .interval(Duration::from_millis(10))
+            .spawn();
+        assert!(events.recv_timeout(Duration::from_millis(50)).is_none());
+        assert!(server.requests_to("topstories.json") > 0);
+
+        drop(events);
+        std::thread::sleep(Duration::from_millis(50));
+        let polled = server.requests_to("topstories.json");
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(server.requests_to("topstories.json"), polled);
+    }
+}